@@ -1,8 +1,14 @@
 use convert_case::{Case, Casing};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 
+use crate::diagnostics::{ConversionStats, Diagnostics};
 use crate::error::{ConversionError, Result};
-use crate::schema::{AdditionalProperties, JsonSchema, SchemaObject, SchemaType, SingleType};
+use crate::options::{ConverterOptions, DefinitionOrder, NullableStyle, PropertyOrder};
+use crate::renderer::{DefaultRenderer, TypeRenderer};
+use crate::schema::{
+    AdditionalProperties, Items, JsonSchema, SchemaObject, SchemaType, SingleType,
+};
 
 /// Converts JSON Schema to Luau type definitions
 pub struct SchemaConverter {
@@ -11,17 +17,88 @@ pub struct SchemaConverter {
 
     /// Set of generated types to avoid duplicates
     generated_types: HashSet<String>,
+
+    /// Output formatting options
+    options: ConverterOptions,
+
+    /// Hooks used to render individual schema shapes
+    renderer: Arc<dyn TypeRenderer>,
+
+    /// Current nesting depth of the conversion
+    depth: usize,
+
+    /// Definitions currently being expanded inline, used to detect cycles
+    inlining: Vec<String>,
+
+    /// Warnings collected during the conversion
+    diagnostics: Diagnostics,
+
+    /// Emitted name of the root type, targeted by `$ref: "#"`
+    root_name: String,
+
+    /// Unique emitted name of every root, in the order the roots are converted
+    root_names: Vec<String>,
+
+    /// Emitted name of the root each definition was extracted from, keyed by definition name
+    definition_roots: HashMap<String, String>,
+
+    /// Keys of definitions a root declared differently from an earlier root, by root and definition name
+    scoped_definitions: HashMap<String, HashMap<String, String>>,
+
+    /// Named root schemas queued by `add_schema`
+    roots: Vec<(String, JsonSchema)>,
+
+    /// String enum unions shared by several properties, paired with their hoisted type name
+    shared_enums: Vec<(String, String)>,
+
+    /// Unique emitted name of every definition, keyed by definition name
+    type_names: HashMap<String, String>,
+
+    /// `$ref`s lenient conversion replaced with `any` in the type being converted
+    unresolved_refs: Vec<String>,
+
+    /// Positions of the tuple typed last, paired with their types for its `@tuple` comment
+    tuple_members: Option<(Vec<JsonSchema>, Vec<String>)>,
+
+    /// Nesting of conversions made only to describe a schema in a comment, which are not counted
+    describing: usize,
 }
 
 impl SchemaConverter {
     /// Create new SchemaConverter
     pub fn new() -> Self {
+        Self::with_options(ConverterOptions::default())
+    }
+
+    /// Create new SchemaConverter with custom options
+    pub fn with_options(options: ConverterOptions) -> Self {
         Self {
             definitions: HashMap::new(),
             generated_types: HashSet::new(),
+            options,
+            renderer: Arc::new(DefaultRenderer),
+            depth: 0,
+            inlining: Vec::new(),
+            diagnostics: Diagnostics::default(),
+            root_name: String::new(),
+            root_names: Vec::new(),
+            definition_roots: HashMap::new(),
+            scoped_definitions: HashMap::new(),
+            roots: Vec::new(),
+            shared_enums: Vec::new(),
+            type_names: HashMap::new(),
+            unresolved_refs: Vec::new(),
+            tuple_members: None,
+            describing: 0,
         }
     }
 
+    /// Use a custom renderer for scalar, object, union and reference types
+    pub fn with_renderer(mut self, renderer: impl TypeRenderer + 'static) -> Self {
+        self.renderer = Arc::new(renderer);
+        self
+    }
+
     /// Convert schema to Luau type definitions with default root name
     pub fn convert(&self, schema: &JsonSchema) -> Result<String> {
         self.convert_with_name(schema, "Root")
@@ -29,52 +106,723 @@ impl SchemaConverter {
 
     /// Convert schema to Luau type definitions with custom type name
     pub fn convert_with_name(&self, schema: &JsonSchema, type_name: &str) -> Result<String> {
+        self.convert_with_diagnostics(schema, type_name)
+            .map(|(output, _)| output)
+    }
+
+    /// Convert schema to Luau type definitions, also returning the collected warnings
+    pub fn convert_with_diagnostics(
+        &self,
+        schema: &JsonSchema,
+        type_name: &str,
+    ) -> Result<(String, Diagnostics)> {
+        self.convert_roots(&[(type_name.to_string(), schema.clone())])
+    }
+
+    /// Queue a named root schema to be emitted by `finish`
+    pub fn add_schema(&mut self, name: &str, schema: &JsonSchema) -> &mut Self {
+        self.roots.push((name.to_string(), schema.clone()));
+        self
+    }
+
+    /// Emit every queued root schema, sharing a single set of definitions
+    pub fn finish(&mut self) -> Result<String> {
+        self.finish_with_diagnostics().map(|(output, _)| output)
+    }
+
+    /// Emit every queued root schema, also returning the collected warnings
+    pub fn finish_with_diagnostics(&mut self) -> Result<(String, Diagnostics)> {
+        let roots = std::mem::take(&mut self.roots);
+        self.convert_roots(&roots)
+    }
+
+    /// Clear definitions, generated types and queued roots so the converter can be reused
+    ///
+    /// Options and the renderer are kept.
+    pub fn reset(&mut self) {
+        self.definitions.clear();
+        self.generated_types.clear();
+        self.depth = 0;
+        self.inlining.clear();
+        self.diagnostics = Diagnostics::default();
+        self.root_name.clear();
+        self.root_names.clear();
+        self.definition_roots.clear();
+        self.scoped_definitions.clear();
+        self.roots.clear();
+        self.shared_enums.clear();
+        self.type_names.clear();
+        self.unresolved_refs.clear();
+        self.tuple_members = None;
+        self.describing = 0;
+    }
+
+    /// Convert one or more named root schemas into a single module
+    fn convert_roots(&self, roots: &[(String, JsonSchema)]) -> Result<(String, Diagnostics)> {
         let mut converter = self.clone();
-        converter.extract_definitions(schema);
+        converter.assign_root_names(roots);
+        for (root_name, (_, schema)) in converter.root_names.clone().iter().zip(roots) {
+            converter.extract_root_definitions(root_name, schema)?;
+        }
+        converter.assign_type_names(roots);
+        for (_, schema) in roots {
+            converter.collect_unsupported_keywords(schema, "#", 0)?;
+        }
+        if converter.options.hoist_shared_enums && !converter.options.inline_all {
+            converter.collect_shared_enums(roots)?;
+        }
 
         let mut output = String::new();
 
-        // Generate main type with PascalCase name
-        let pascal_type_name = type_name.to_case(Case::Pascal);
-        let main_type = converter.convert_schema(schema, &pascal_type_name, 0)?;
-        output.push_str(&main_type);
+        // The mode directive must precede every other line
+        if let Some(mode) = converter.options.mode {
+            output.push_str(mode.directive());
+            output.push_str("\n\n");
+        }
 
-        // Generate definitions
-        converter.generate_definitions(&mut output)?;
+        // Record the input file the output was generated from
+        if let Some(source) = &converter.options.source_comment
+            && converter.options.emit_comments
+        {
+            output.push_str(&format!(
+                "{}--- Source: {}\n\n",
+                Self::create_indent(converter.options.base_indent),
+                source
+            ));
+        }
+
+        // Record which schema produced the output
+        if converter.options.emit_provenance
+            && converter.options.emit_comments
+            && let Some((_, schema)) = roots.first()
+        {
+            converter.generate_provenance(schema, &mut output);
+        }
+
+        if !converter.options.definitions_only {
+            for (i, (type_name, schema)) in roots.iter().enumerate() {
+                if i > 0 {
+                    output.push_str(&converter.declaration_separator());
+                }
+
+                // Give a quick overview of the root defaults
+                if converter.options.emit_default_summary && converter.options.emit_comments {
+                    converter.generate_default_summary(schema, &mut output);
+                }
+
+                // Generate main type with PascalCase name, preferring the title when enabled
+                let pascal_type_name = converter.root_names[i].clone();
+                converter
+                    .warn_invalid_type_name(converter.schema_title(schema).unwrap_or(type_name));
+                converter.root_name = pascal_type_name.clone();
+                let main_type = converter.convert_schema(
+                    schema,
+                    &pascal_type_name,
+                    converter.options.base_indent,
+                )?;
+                output.push_str(&main_type);
+            }
+        }
+
+        // Generate definitions unless they were all expanded inline
+        if !converter.options.inline_all {
+            let mut definitions = String::new();
+            converter.generate_definitions(roots, &mut definitions)?;
+            if converter.options.definitions_only {
+                output.push_str(definitions.trim_start_matches('\n'));
+            } else {
+                output.push_str(&definitions);
+            }
+        }
 
         // Ensure exactly one newline at EOF
         if !output.ends_with('\n') {
             output.push('\n');
         }
 
-        // Append return {} so standard Luau can require the module
-        output.push_str("\nreturn {}\n");
+        // Summarize how many types were generated
+        if converter.options.emit_summary && converter.options.emit_comments {
+            let count = converter.generated_types.len();
+            output.push_str(&format!(
+                "\n{}--- Generated {} type{} from schema\n",
+                Self::create_indent(converter.options.base_indent),
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+
+        // Append return {} so standard Luau can require the module; declaration files have no runtime code
+        if !converter.options.declarations {
+            output.push_str("\nreturn {}\n");
+        }
+
+        Ok((output, converter.diagnostics))
+    }
+
+    /// Record keywords the output cannot fully represent, with the JSON pointer of their schema
+    fn collect_unsupported_keywords(
+        &mut self,
+        schema: &JsonSchema,
+        path: &str,
+        depth: usize,
+    ) -> Result<()> {
+        self.check_walk_depth(depth)?;
+        let JsonSchema::Object(obj) = schema else {
+            return Ok(());
+        };
+
+        if obj.not.is_some() && (obj.type_.is_none() || Self::not_excluded_types(obj).is_none()) {
+            self.diagnostics.unsupported("not", path);
+        }
+        if obj.pattern_properties.as_ref().is_some_and(|patterns| {
+            patterns
+                .keys()
+                .any(|pattern| Self::literal_pattern_key(pattern).is_none())
+        }) {
+            self.diagnostics.unsupported("patternProperties", path);
+        }
+        if obj.if_.is_some() && Self::conditional_discriminator(obj).is_none() {
+            self.diagnostics.unsupported("if", path);
+        }
+
+        // Keywords the schema model does not know land in the extensions, next to `x-` annotations
+        let mut unknown: Vec<_> = obj
+            .extensions
+            .keys()
+            .filter(|keyword| !keyword.starts_with("x-"))
+            .collect();
+        unknown.sort();
+        for keyword in unknown {
+            self.diagnostics.unsupported(keyword.as_str(), path);
+        }
+
+        let escape = |key: &str| key.replace('~', "~0").replace('/', "~1");
+        let mut children: Vec<(String, &JsonSchema)> = Vec::new();
+        for (keyword, map) in [
+            ("properties", &obj.properties),
+            ("patternProperties", &obj.pattern_properties),
+            ("definitions", &obj.definitions),
+            ("$defs", &obj.defs),
+        ] {
+            let mut entries: Vec<_> = map.iter().flatten().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, child) in entries {
+                children.push((format!("{}/{}/{}", path, keyword, escape(key)), child));
+            }
+        }
+        let tuple_items = match &obj.items {
+            Some(Items::Tuple(positions)) => Some(positions),
+            _ => None,
+        };
+        for (keyword, list) in [
+            ("prefixItems", obj.prefix_items.as_ref()),
+            ("items", tuple_items),
+            ("allOf", obj.all_of.as_ref()),
+            ("anyOf", obj.any_of.as_ref()),
+            ("oneOf", obj.one_of.as_ref()),
+        ] {
+            for (i, child) in list.into_iter().flatten().enumerate() {
+                children.push((format!("{}/{}/{}", path, keyword, i), child));
+            }
+        }
+        if let Some(Items::Single(child)) = &obj.items {
+            children.push((format!("{}/items", path), child));
+        }
+        for (keyword, child) in [
+            ("additionalItems", &obj.additional_items),
+            ("contains", &obj.contains),
+            ("not", &obj.not),
+            ("if", &obj.if_),
+            ("then", &obj.then_),
+            ("else", &obj.else_),
+        ] {
+            if let Some(child) = child {
+                children.push((format!("{}/{}", path, keyword), child));
+            }
+        }
+        if let Some(AdditionalProperties::Schema(child)) = &obj.additional_properties {
+            children.push((format!("{}/additionalProperties", path), child));
+        }
+
+        for (child_path, child) in children {
+            self.collect_unsupported_keywords(child, &child_path, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Line breaks written between two top-level declarations
+    fn declaration_separator(&self) -> String {
+        "\n".repeat(self.options.blank_lines_between + 1)
+    }
+
+    /// Generate `$schema` and `$id` provenance comments
+    fn generate_provenance(&self, schema: &JsonSchema, output: &mut String) {
+        if let JsonSchema::Object(obj) = schema {
+            let indent_str = Self::create_indent(self.options.base_indent);
+            let start = output.len();
+
+            if let Some(uri) = &obj.schema {
+                output.push_str(&format!("{}--- @schema {}\n", indent_str, uri));
+            }
+            if let Some(id) = &obj.id {
+                output.push_str(&format!("{}--- @id {}\n", indent_str, id));
+            }
+
+            if output.len() > start {
+                output.push('\n');
+            }
+        }
+    }
+
+    /// Generate a `@default` comment listing every root property default
+    fn generate_default_summary(&self, schema: &JsonSchema, output: &mut String) {
+        let JsonSchema::Object(obj) = schema else {
+            return;
+        };
+        let mut defaults: Vec<_> = obj
+            .properties
+            .iter()
+            .flatten()
+            .filter_map(|(name, prop)| match prop {
+                JsonSchema::Object(prop_obj) => prop_obj.default.as_ref().map(|d| (name, d)),
+                JsonSchema::Boolean(_) => None,
+            })
+            .collect();
+        if defaults.is_empty() {
+            return;
+        }
+        defaults.sort_by(|a, b| a.0.cmp(b.0));
+
+        let fields: Vec<_> = defaults
+            .into_iter()
+            .map(|(name, value)| {
+                format!("{} = {}", Self::luau_key(name), json_value_to_luau(value))
+            })
+            .collect();
+        output.push_str(&format!(
+            "{}--- @default {{ {} }}\n",
+            Self::create_indent(self.options.base_indent),
+            fields.join(", ")
+        ));
+    }
+
+    /// Format a table key, bracketing keys that are not valid identifiers
+    fn luau_key(key: &str) -> String {
+        if Self::is_luau_identifier(key) {
+            key.to_string()
+        } else {
+            format!("[{}]", Self::luau_string(key))
+        }
+    }
+
+    /// Format a quoted Luau string literal, escaping quotes, backslashes and control characters
+    fn luau_string(value: &str) -> String {
+        let mut literal = String::with_capacity(value.len() + 2);
+        literal.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => literal.push_str("\\\""),
+                '\\' => literal.push_str("\\\\"),
+                '\n' => literal.push_str("\\n"),
+                '\r' => literal.push_str("\\r"),
+                '\t' => literal.push_str("\\t"),
+                c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => literal.push(c),
+            }
+        }
+        literal.push('"');
+        literal
+    }
+
+    /// Check whether a name can be written as a bare Luau identifier
+    fn is_luau_identifier(name: &str) -> bool {
+        const KEYWORDS: [&str; 21] = [
+            "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in",
+            "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+        ];
+        name.chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !KEYWORDS.contains(&name)
+    }
+
+    /// Generate an `@<name>` comment for each `x-<name>` vendor extension
+    fn format_extensions(&self, obj: &SchemaObject, indent_str: &str) -> String {
+        let mut output = String::new();
+        if !self.options.emit_extensions || !self.options.emit_comments {
+            return output;
+        }
+
+        let mut extensions: Vec<_> = obj
+            .extensions
+            .iter()
+            .filter_map(|(key, value)| key.strip_prefix("x-").map(|name| (name, value)))
+            .collect();
+        extensions.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in extensions {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                _ => value.to_string(),
+            };
+            // Every line of a multi-line value stays inside the comment
+            let mut lines = value.lines();
+            output.push_str(&format!(
+                "{}--- @{} {}\n",
+                indent_str,
+                name,
+                lines.next().unwrap_or_default()
+            ));
+            for line in lines {
+                output.push_str(&format!("{}--- {}\n", indent_str, line));
+            }
+        }
+        output
+    }
+
+    /// Generate a `@jsonschema` comment holding the schema's constraints as JSON
+    ///
+    /// Keys are sorted and whole numbers are written without a fraction so tooling can recover them.
+    fn format_schema_metadata(&self, obj: &SchemaObject, indent_str: &str) -> String {
+        if !self.options.emit_schema_metadata || !self.options.emit_comments {
+            return String::new();
+        }
+        let number = |n: f64| -> serde_json::Value {
+            if n.fract() == 0.0 && n.abs() < 1e15 {
+                (n as i64).into()
+            } else {
+                n.into()
+            }
+        };
+        let constraints = [
+            ("minimum", obj.minimum.map(number)),
+            ("maximum", obj.maximum.map(number)),
+            ("exclusiveMinimum", obj.exclusive_minimum.map(number)),
+            ("exclusiveMaximum", obj.exclusive_maximum.map(number)),
+            ("multipleOf", obj.multiple_of.map(number)),
+            ("minLength", obj.min_length.map(Into::into)),
+            ("maxLength", obj.max_length.map(Into::into)),
+            ("pattern", obj.pattern.clone().map(Into::into)),
+            ("format", obj.format.clone().map(Into::into)),
+            ("minItems", obj.min_items.map(Into::into)),
+            ("maxItems", obj.max_items.map(Into::into)),
+            ("uniqueItems", obj.unique_items.map(Into::into)),
+            ("minProperties", obj.min_properties.map(Into::into)),
+            ("maxProperties", obj.max_properties.map(Into::into)),
+        ];
+        let metadata: serde_json::Map<_, _> = constraints
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| (key.to_string(), value)))
+            .collect();
+        if metadata.is_empty() {
+            return String::new();
+        }
+        format!(
+            "{}--- @jsonschema {}\n",
+            indent_str,
+            serde_json::Value::Object(metadata)
+        )
+    }
+
+    /// Generate `@example` comments, warning about examples the schema rejects
+    fn format_examples(&mut self, obj: &SchemaObject, label: &str, indent_str: &str) -> String {
+        let mut output = String::new();
+        if !self.options.emit_examples || !self.options.emit_comments {
+            return output;
+        }
+
+        let examples = obj.examples.as_deref().unwrap_or_default();
+        let shown = self
+            .options
+            .max_examples
+            .map_or(examples.len(), |max| max.min(examples.len()));
+        for (i, example) in examples.iter().enumerate() {
+            if i < shown {
+                output.push_str(&format!(
+                    "{}--- @example {}\n",
+                    indent_str,
+                    json_value_to_luau(example)
+                ));
+            }
+            if !self.example_matches(obj, example) {
+                self.diagnostics.warn(format!(
+                    "example {} for '{}' does not match its schema",
+                    example, label
+                ));
+            }
+        }
+        if shown < examples.len() {
+            output.push_str(&format!(
+                "{}--- @example ... (+{} more)\n",
+                indent_str,
+                examples.len() - shown
+            ));
+        }
+        output
+    }
+
+    /// Check an example against the schema's enum, const and type
+    fn example_matches(&self, obj: &SchemaObject, value: &serde_json::Value) -> bool {
+        let obj = self.resolve_reference_if_needed(obj);
+        if let Some(values) = &obj.enum_ {
+            return values.contains(value);
+        }
+        if let Some(const_value) = &obj.const_ {
+            return const_value == value;
+        }
+        match &obj.type_ {
+            Some(type_) => Self::get_single_types(type_)
+                .into_iter()
+                .any(|t| Self::value_has_type(value, t)),
+            None => true,
+        }
+    }
+
+    /// Check whether a JSON value is an instance of a schema type
+    fn value_has_type(value: &serde_json::Value, type_: &SingleType) -> bool {
+        match type_ {
+            SingleType::Null => value.is_null(),
+            SingleType::Boolean => value.is_boolean(),
+            SingleType::String => value.is_string(),
+            SingleType::Number => value.is_number(),
+            SingleType::Integer => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+            SingleType::Array => value.is_array(),
+            SingleType::Object => value.is_object(),
+        }
+    }
 
-        Ok(output)
+    /// Extract one root's definitions into the shared set
+    ///
+    /// A definition another root already declared with a different schema is stored under a
+    /// key scoped to this root, so it is emitted as its own suffixed type.
+    fn extract_root_definitions(&mut self, root_name: &str, schema: &JsonSchema) -> Result<()> {
+        let shared = std::mem::take(&mut self.definitions);
+        self.extract_definitions(schema)?;
+        let mut extracted: Vec<_> = std::mem::replace(&mut self.definitions, shared)
+            .into_iter()
+            .collect();
+        extracted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (def_name, def_schema) in extracted {
+            let key = match self.definitions.get(&def_name) {
+                None => def_name,
+                Some(existing) if *existing == def_schema => continue,
+                Some(_) => {
+                    let key = format!("{}#{}", root_name, def_name);
+                    self.scoped_definitions
+                        .entry(root_name.to_string())
+                        .or_default()
+                        .insert(def_name, key.clone());
+                    key
+                }
+            };
+            self.definition_roots
+                .insert(key.clone(), root_name.to_string());
+            self.definitions.insert(key, def_schema);
+        }
+        Ok(())
     }
 
     /// Extract definitions from schema object
-    fn extract_definitions(&mut self, schema: &JsonSchema) {
+    fn extract_definitions(&mut self, schema: &JsonSchema) -> Result<()> {
         if let JsonSchema::Object(obj) = schema {
             // Extract from both definitions and $defs
             for defs in [&obj.definitions, &obj.defs].into_iter().flatten() {
                 self.definitions.extend(defs.clone());
             }
+            for defs in [&obj.definitions, &obj.defs].into_iter().flatten() {
+                self.extract_nested_definitions(defs, "", 1)?;
+            }
+            self.extract_property_definitions(obj, 1)?;
         }
+        Ok(())
     }
 
-    /// Generate all definition types in sorted order
-    fn generate_definitions(&mut self, output: &mut String) -> Result<()> {
+    /// Extract `$defs`/`definitions` blocks declared inside other definitions
+    ///
+    /// Nested definitions are keyed by their pointer below the top-level definitions, such
+    /// as `Outer/$defs/Inner`, so they never stand in for a top-level definition.
+    fn extract_nested_definitions(
+        &mut self,
+        defs: &HashMap<String, JsonSchema>,
+        prefix: &str,
+        depth: usize,
+    ) -> Result<()> {
+        self.check_walk_depth(depth)?;
+        let mut names: Vec<_> = defs.keys().collect();
+        names.sort();
+        for name in names {
+            if let JsonSchema::Object(def_obj) = &defs[name] {
+                for (keyword, nested) in [
+                    ("definitions", &def_obj.definitions),
+                    ("$defs", &def_obj.defs),
+                ] {
+                    let Some(nested) = nested else {
+                        continue;
+                    };
+                    let nested_prefix = format!("{}{}/{}/", prefix, name, keyword);
+                    for (def_name, def_schema) in nested {
+                        self.definitions
+                            .insert(format!("{}{}", nested_prefix, def_name), def_schema.clone());
+                    }
+                    self.extract_nested_definitions(nested, &nested_prefix, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract `$defs`/`definitions` blocks nested inside property subschemas
+    ///
+    /// Definitions declared closer to the root win when names collide.
+    fn extract_property_definitions(&mut self, obj: &SchemaObject, depth: usize) -> Result<()> {
+        self.check_walk_depth(depth)?;
+        let Some(properties) = &obj.properties else {
+            return Ok(());
+        };
+        let mut names: Vec<_> = properties.keys().collect();
+        names.sort();
+        for name in names {
+            if let JsonSchema::Object(prop_obj) = &properties[name] {
+                for defs in [&prop_obj.definitions, &prop_obj.defs]
+                    .into_iter()
+                    .flatten()
+                {
+                    for (def_name, def_schema) in defs {
+                        self.definitions
+                            .entry(def_name.clone())
+                            .or_insert_with(|| def_schema.clone());
+                    }
+                }
+                self.extract_property_definitions(prop_obj, depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Find string enums repeated across properties and name them after their first property
+    fn collect_shared_enums(&mut self, roots: &[(String, JsonSchema)]) -> Result<()> {
         let mut def_names: Vec<_> = self.definitions.keys().cloned().collect();
         def_names.sort();
 
-        for def_name in def_names {
-            let pascal_def_name = def_name.to_case(Case::Pascal);
+        let mut enums: Vec<(String, String, usize)> = Vec::new();
+        for (_, schema) in roots {
+            self.count_property_enums(schema, &mut enums, 0)?;
+        }
+        for def_name in &def_names {
+            if let Some(def_schema) = self.definitions.get(def_name).cloned() {
+                self.count_property_enums(&def_schema, &mut enums, 0)?;
+            }
+        }
+
+        let mut taken: HashSet<String> = def_names
+            .iter()
+            .map(|def_name| self.definition_type_name(def_name))
+            .chain(roots.iter().map(|(name, _)| self.type_name(name)))
+            .collect();
+        for (union, prop_name, count) in enums {
+            if count < 2 {
+                continue;
+            }
+            let base = self.type_name(&prop_name);
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while taken.contains(&name) {
+                name = format!("{}{}", base, suffix);
+                suffix += 1;
+            }
+            taken.insert(name.clone());
+            self.shared_enums.push((union, name));
+        }
+        Ok(())
+    }
+
+    /// Count the string enums of every nested property as `(union, first property, count)`
+    fn count_property_enums(
+        &self,
+        schema: &JsonSchema,
+        enums: &mut Vec<(String, String, usize)>,
+        depth: usize,
+    ) -> Result<()> {
+        self.check_walk_depth(depth)?;
+        let JsonSchema::Object(obj) = schema else {
+            return Ok(());
+        };
+        match &obj.items {
+            Some(Items::Single(items)) => self.count_property_enums(items, enums, depth + 1)?,
+            Some(Items::Tuple(positions)) => {
+                for position in positions {
+                    self.count_property_enums(position, enums, depth + 1)?;
+                }
+            }
+            None => {}
+        }
+        let Some(properties) = &obj.properties else {
+            return Ok(());
+        };
+        let mut prop_names: Vec<_> = properties.keys().collect();
+        prop_names.sort();
+        for prop_name in prop_names {
+            let prop_schema = &properties[prop_name];
+            if let JsonSchema::Object(prop_obj) = prop_schema
+                && let Some(values) = &prop_obj.enum_
+                && values.len() > 1
+                && values.iter().all(|v| v.is_string())
+            {
+                let union = self.convert_enum(values);
+                match enums.iter_mut().find(|(existing, _, _)| *existing == union) {
+                    Some((_, _, count)) => *count += 1,
+                    None => enums.push((union, prop_name.clone(), 1)),
+                }
+            }
+            self.count_property_enums(prop_schema, enums, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Generate all definition types in sorted order
+    fn generate_definitions(
+        &mut self,
+        roots: &[(String, JsonSchema)],
+        output: &mut String,
+    ) -> Result<()> {
+        let mut banner = self.options.definitions_banner.clone();
+
+        // Shared enums come first, in the order they were found
+        for (union, name) in self.shared_enums.clone() {
+            self.push_definitions_banner(&mut banner, output);
+            self.generated_types.insert(name.clone());
+            output.push_str(&format!(
+                "{}{}{} {} = {}",
+                self.declaration_separator(),
+                Self::create_indent(self.options.base_indent),
+                self.type_keyword(&name),
+                name,
+                union
+            ));
+        }
+
+        for def_name in self.ordered_definition_names(roots)? {
+            // Definitions standing in for built-in types are never redeclared
+            if self.roblox_type(&def_name).is_some() {
+                continue;
+            }
+
+            let pascal_def_name = self.definition_type_name(&def_name);
             if !self.generated_types.contains(&pascal_def_name)
                 && let Some(def_schema) = self.definitions.get(&def_name).cloned()
             {
-                output.push_str("\n\n");
-                let def_type = self.convert_schema(&def_schema, &pascal_def_name, 0)?;
+                self.warn_invalid_type_name(&self.definition_base_name(&def_name));
+                self.warn_colliding_type_name(&def_name);
+                self.push_definitions_banner(&mut banner, output);
+
+                // `$ref: "#"` inside a definition targets the root that declared it
+                if let Some(root_name) = self.definition_roots.get(&def_name) {
+                    self.root_name = root_name.clone();
+                }
+                output.push_str(&self.declaration_separator());
+                let def_type =
+                    self.convert_schema(&def_schema, &pascal_def_name, self.options.base_indent)?;
                 output.push_str(&def_type);
             }
         }
@@ -82,13 +830,159 @@ impl SchemaConverter {
         Ok(())
     }
 
+    /// Write the banner comment once, ahead of the first definition
+    fn push_definitions_banner(&self, banner: &mut Option<String>, output: &mut String) {
+        if let Some(text) = banner.take()
+            && self.options.emit_comments
+        {
+            output.push_str(&format!(
+                "{}{}--- {}",
+                self.declaration_separator(),
+                Self::create_indent(self.options.base_indent),
+                text
+            ));
+        }
+    }
+
+    /// List definition names in the configured emission order
+    fn ordered_definition_names(&self, roots: &[(String, JsonSchema)]) -> Result<Vec<String>> {
+        let mut def_names: Vec<_> = self.definitions.keys().cloned().collect();
+        def_names.sort();
+        if self.options.definition_order == DefinitionOrder::Reference {
+            let referenced = self.definitions_by_reference(roots)?;
+            // Stable sort keeps unreferenced definitions alphabetical at the end
+            def_names.sort_by_key(|name| {
+                referenced
+                    .iter()
+                    .position(|r| r == name)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+        Ok(def_names)
+    }
+
+    /// Walk the roots breadth-first, listing definitions in the order they are first referenced
+    fn definitions_by_reference(&self, roots: &[(String, JsonSchema)]) -> Result<Vec<String>> {
+        let mut order: Vec<String> = Vec::new();
+        let mut queue: VecDeque<&JsonSchema> = roots.iter().map(|(_, schema)| schema).collect();
+        while let Some(schema) = queue.pop_front() {
+            let mut refs = Vec::new();
+            self.collect_refs(schema, &mut refs, 0)?;
+            for def_name in refs {
+                if !order.contains(&def_name)
+                    && let Some(def_schema) = self.definitions.get(&def_name)
+                {
+                    queue.push_back(def_schema);
+                    order.push(def_name);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Collect the definition names a schema references, in the order its fields are written
+    fn collect_refs(
+        &self,
+        schema: &JsonSchema,
+        refs: &mut Vec<String>,
+        depth: usize,
+    ) -> Result<()> {
+        self.check_walk_depth(depth)?;
+        let JsonSchema::Object(obj) = schema else {
+            return Ok(());
+        };
+
+        if let Some(def_name) = obj.ref_.as_deref().and_then(|ref_path| {
+            ref_path
+                .strip_prefix("#/$defs/")
+                .or_else(|| ref_path.strip_prefix("#/definitions/"))
+        }) {
+            refs.push(def_name.to_string());
+        }
+        if let Some(properties) = &obj.properties {
+            let required: HashSet<String> = obj.required.iter().flatten().cloned().collect();
+            for name in self.ordered_property_names(properties, &required) {
+                self.collect_refs(&properties[&name], refs, depth + 1)?;
+            }
+        }
+        let mut patterns: Vec<_> = obj.pattern_properties.iter().flatten().collect();
+        patterns.sort_by(|a, b| a.0.cmp(b.0));
+        for (_, pattern_schema) in patterns {
+            self.collect_refs(pattern_schema, refs, depth + 1)?;
+        }
+        if let Some(AdditionalProperties::Schema(additional)) = &obj.additional_properties {
+            self.collect_refs(additional, refs, depth + 1)?;
+        }
+        match &obj.items {
+            Some(Items::Single(items)) => self.collect_refs(items, refs, depth + 1)?,
+            Some(Items::Tuple(positions)) => {
+                for position in positions {
+                    self.collect_refs(position, refs, depth + 1)?;
+                }
+            }
+            None => {}
+        }
+        for child in [
+            &obj.additional_items,
+            &obj.contains,
+            &obj.not,
+            &obj.if_,
+            &obj.then_,
+            &obj.else_,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.collect_refs(child, refs, depth + 1)?;
+        }
+        for list in [&obj.prefix_items, &obj.all_of, &obj.any_of, &obj.one_of] {
+            for child in list.iter().flatten() {
+                self.collect_refs(child, refs, depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Main schema conversion entry point
     fn convert_schema(&mut self, schema: &JsonSchema, name: &str, indent: usize) -> Result<String> {
-        match schema {
-            JsonSchema::Boolean(true) => Ok("any".to_string()),
-            JsonSchema::Boolean(false) => Ok("never".to_string()),
+        self.enter_nested()?;
+        let outer_refs = std::mem::take(&mut self.unresolved_refs);
+        let result = match schema {
+            JsonSchema::Boolean(value) => {
+                self.generated_types.insert(name.to_string());
+                Ok(format!(
+                    "{}{} {} = {}",
+                    Self::create_indent(indent),
+                    self.type_keyword(name),
+                    name,
+                    if *value { "any" } else { "never" }
+                ))
+            }
             JsonSchema::Object(obj) => self.convert_object(obj, name, indent),
+        };
+        self.depth -= 1;
+
+        // Record references used by the declaration itself rather than by one of its fields
+        let refs = std::mem::replace(&mut self.unresolved_refs, outer_refs);
+        let refs = self.format_unresolved_refs(&refs, &Self::create_indent(indent));
+        result.map(|result| format!("{}{}", refs, result))
+    }
+
+    /// Track one more level of nesting, failing once the depth limit is exceeded
+    fn enter_nested(&mut self) -> Result<()> {
+        self.check_walk_depth(self.depth)?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Fail once a schema nested `depth` levels down reaches the depth limit
+    fn check_walk_depth(&self, depth: usize) -> Result<()> {
+        if depth >= self.options.max_depth {
+            return Err(ConversionError::InvalidSchema(
+                "max depth exceeded".to_string(),
+            ));
         }
+        Ok(())
     }
 
     /// Extract single types from SchemaType
@@ -99,27 +993,100 @@ impl SchemaConverter {
         }
     }
 
+    /// Get the declared types, minus any excluded by a type-only `not`
+    fn effective_types(obj: &SchemaObject) -> Option<Vec<&SingleType>> {
+        let mut types = Self::get_single_types(obj.type_.as_ref()?);
+        if let Some(excluded) = Self::not_excluded_types(obj) {
+            types.retain(|t| {
+                !excluded
+                    .iter()
+                    .any(|e| e == t || (**e == SingleType::Number && **t == SingleType::Integer))
+            });
+        }
+        Some(types)
+    }
+
+    /// Get the types excluded by a `not` that constrains nothing but `type`
+    fn not_excluded_types(obj: &SchemaObject) -> Option<Vec<&SingleType>> {
+        let JsonSchema::Object(not) = obj.not.as_deref()? else {
+            return None;
+        };
+        let type_ = not.type_.as_ref()?;
+        let type_only = SchemaObject {
+            type_: Some(type_.clone()),
+            ..Default::default()
+        };
+        (**not == type_only).then(|| Self::get_single_types(type_))
+    }
+
+    /// Document a `not` that cannot be expressed by narrowing the declared type
+    fn format_not_comment(&mut self, obj: &SchemaObject, indent_str: &str) -> Result<String> {
+        let Some(not) = &obj.not else {
+            return Ok(String::new());
+        };
+        if !self.options.emit_comments
+            || (obj.type_.is_some() && Self::not_excluded_types(obj).is_some())
+        {
+            return Ok(String::new());
+        }
+        self.describing += 1;
+        let result = self.inline_type_with_refs(not);
+        self.describing -= 1;
+        let (excluded, refs) = result?;
+        Ok(format!(
+            "{}{}--- @not {}\n",
+            self.format_unresolved_refs(&refs, indent_str),
+            indent_str,
+            excluded
+        ))
+    }
+
     /// Convert schema object to type definition
     fn convert_object(&mut self, obj: &SchemaObject, name: &str, indent: usize) -> Result<String> {
         let indent_str = Self::create_indent(indent);
 
         // Add description as comment if present
-        let description_comment = if let Some(desc) = &obj.description {
+        let mut description_comment = if let Some(desc) = &obj.description
+            && self.options.emit_comments
+            && self.options.emit_type_descriptions
+        {
             format!("{}--- {}\n", indent_str, desc)
         } else {
             String::new()
         };
+        description_comment.push_str(&self.format_extensions(obj, &indent_str));
+        description_comment.push_str(&self.format_schema_metadata(obj, &indent_str));
+        description_comment.push_str(&self.format_examples(obj, name, &indent_str));
+        description_comment.push_str(&self.format_not_comment(obj, &indent_str)?);
 
         // Handle references
         if let Some(ref_path) = &obj.ref_ {
+            self.generated_types.insert(name.to_string());
             return self.resolve_ref(ref_path).map(|resolved| {
                 format!(
-                    "{}{}export type {} = {}",
-                    description_comment, indent_str, name, resolved
+                    "{}{}{} {} = {}",
+                    description_comment,
+                    indent_str,
+                    self.type_keyword(name),
+                    name,
+                    resolved
                 )
             });
         }
 
+        // Handle formats describing Roblox built-in types
+        if let Some(builtin) = obj.format.as_deref().and_then(|f| self.roblox_type(f)) {
+            self.generated_types.insert(name.to_string());
+            return Ok(format!(
+                "{}{}{} {} = {}",
+                description_comment,
+                indent_str,
+                self.type_keyword(name),
+                name,
+                builtin
+            ));
+        }
+
         // Handle composition types (allOf, anyOf, oneOf)
         if let Some(mut result) = self.handle_composition_types(obj, name, indent)? {
             if !description_comment.is_empty() {
@@ -131,6 +1098,14 @@ impl SchemaConverter {
         // Handle enum and const values
         if let Some(enum_values) = &obj.enum_ {
             let mut result = self.generate_enum_type(enum_values, name, &indent_str)?;
+            // Keep constraint comments such as @format alongside the literal union
+            let constraints = self.format_constraints_with_indent(
+                &JsonSchema::Object(Box::new(obj.clone())),
+                &indent_str,
+            );
+            if !constraints.is_empty() {
+                result = format!("{}{}", constraints, result);
+            }
             if !description_comment.is_empty() {
                 result = format!("{}{}", description_comment, result);
             }
@@ -139,6 +1114,13 @@ impl SchemaConverter {
 
         if let Some(const_value) = &obj.const_ {
             let mut result = self.generate_const_type(const_value, name, &indent_str)?;
+            let constraints = self.format_constraints_with_indent(
+                &JsonSchema::Object(Box::new(obj.clone())),
+                &indent_str,
+            );
+            if !constraints.is_empty() {
+                result = format!("{}{}", constraints, result);
+            }
             if !description_comment.is_empty() {
                 result = format!("{}{}", description_comment, result);
             }
@@ -160,6 +1142,38 @@ impl SchemaConverter {
         name: &str,
         indent: usize,
     ) -> Result<Option<String>> {
+        // A conditional keyed on a property const becomes a tagged union
+        if let Some((discriminator, conditional)) = self.conditional_as_one_of(obj)? {
+            let comment = format!("Tagged union (if/then/else on `{}`)", discriminator);
+            let branches = match self.merge_base_into_branches(&conditional)? {
+                Some(branches) => branches,
+                None => conditional.one_of.unwrap_or_default(),
+            };
+            return self.handle_union_type(&branches, name, indent, "oneOf", &comment);
+        }
+
+        // Shared properties alongside oneOf/anyOf are merged into every branch
+        if let Some(branches) = self.merge_base_into_branches(obj)? {
+            let (kind, comment) = if obj.one_of.is_some() {
+                ("oneOf", "Union type (exactly one of these types)")
+            } else {
+                ("anyOf", "Union type (any of these types)")
+            };
+            return self.handle_union_type(&branches, name, indent, kind, comment);
+        }
+
+        // A union of bare consts is an enum written as a plain literal union
+        if let Some(values) = Self::const_branch_values(obj) {
+            let indent_str = Self::create_indent(indent);
+            let constraints = self.format_constraints_with_indent(
+                &JsonSchema::Object(Box::new(obj.clone())),
+                &indent_str,
+            );
+            return self
+                .generate_enum_type(&values, name, &indent_str)
+                .map(|result| Some(format!("{}{}", constraints, result)));
+        }
+
         match self.get_composition_type(obj) {
             Some(("allOf", schemas)) => self.handle_all_of(obj, schemas, name, indent),
             Some(("anyOf", schemas)) => self.handle_union_type(
@@ -180,6 +1194,24 @@ impl SchemaConverter {
         }
     }
 
+    /// Get the values of an `anyOf`/`oneOf` whose branches are all `const` schemas
+    fn const_branch_values(obj: &SchemaObject) -> Option<Vec<serde_json::Value>> {
+        if obj.all_of.is_some() {
+            return None;
+        }
+        let branches = obj.any_of.as_ref().or(obj.one_of.as_ref())?;
+        if branches.is_empty() {
+            return None;
+        }
+        branches
+            .iter()
+            .map(|branch| match branch {
+                JsonSchema::Object(branch) => branch.const_.clone(),
+                JsonSchema::Boolean(_) => None,
+            })
+            .collect()
+    }
+
     /// Get composition type and schemas if present
     fn get_composition_type<'a>(
         &self,
@@ -265,13 +1297,155 @@ impl SchemaConverter {
         Ok(merged)
     }
 
+    /// Rewrite an `if` that tests a single property `const` as a `oneOf` of the `then` and `else` shapes
+    ///
+    /// The `then` branch pins the discriminator to its const. The `else` branch, or an empty
+    /// shape when there is no `else`, narrows an enum discriminator to its other values and is
+    /// dropped when none remain; other discriminators keep their base type in `else`. Returns
+    /// the discriminator name alongside the rewritten schema.
+    fn conditional_as_one_of(
+        &mut self,
+        obj: &SchemaObject,
+    ) -> Result<Option<(String, SchemaObject)>> {
+        let (Some((discriminator, properties)), Some(then_schema)) =
+            (Self::conditional_discriminator(obj), obj.then_.as_deref())
+        else {
+            return Ok(None);
+        };
+
+        let tag = SchemaObject {
+            type_: Some(SchemaType::Single(SingleType::Object)),
+            properties: Some(properties.clone()),
+            required: Some(vec![discriminator.clone()]),
+            ..Default::default()
+        };
+        let then_branch = match then_schema {
+            JsonSchema::Object(_) => {
+                self.merge_all_of_schemas(&tag, std::slice::from_ref(then_schema))?
+            }
+            JsonSchema::Boolean(_) => tag,
+        };
+        // The second branch pins an enum discriminator to the values the `if` does not match
+        let negated_values = Self::negated_tag_values(obj, discriminator);
+        let negated_tag = negated_values.clone().map(|values| SchemaObject {
+            type_: Some(SchemaType::Single(SingleType::Object)),
+            properties: Some(HashMap::from([(
+                discriminator.clone(),
+                JsonSchema::Object(Box::new(SchemaObject {
+                    enum_: Some(values),
+                    ..Default::default()
+                })),
+            )])),
+            required: Some(vec![discriminator.clone()]),
+            ..Default::default()
+        });
+        let mut branches = vec![JsonSchema::Object(Box::new(then_branch))];
+        match (obj.else_.as_deref(), negated_tag) {
+            // No other tag value is left, so only the `then` shape can match
+            _ if negated_values.is_some_and(|values| values.is_empty()) => {}
+            (Some(else_schema @ JsonSchema::Object(_)), Some(tag)) => {
+                let else_branch =
+                    self.merge_all_of_schemas(&tag, std::slice::from_ref(else_schema))?;
+                branches.push(JsonSchema::Object(Box::new(else_branch)));
+            }
+            (Some(else_schema), _) => branches.push(else_schema.clone()),
+            (None, Some(tag)) => branches.push(JsonSchema::Object(Box::new(tag))),
+            (None, None) => {}
+        }
+
+        let mut conditional = obj.clone();
+        conditional.if_ = None;
+        conditional.then_ = None;
+        conditional.else_ = None;
+        conditional.one_of = Some(branches);
+        Ok(Some((discriminator.clone(), conditional)))
+    }
+
+    /// Get the property tested by an `if` that checks nothing but a single property `const`
+    fn conditional_discriminator(
+        obj: &SchemaObject,
+    ) -> Option<(&String, &HashMap<String, JsonSchema>)> {
+        let JsonSchema::Object(if_obj) = obj.if_.as_deref()? else {
+            return None;
+        };
+        if obj.then_.is_none() || obj.one_of.is_some() || obj.any_of.is_some() {
+            return None;
+        }
+        let properties = if_obj.properties.as_ref()?;
+        let (discriminator, JsonSchema::Object(condition)) = properties.iter().next()? else {
+            return None;
+        };
+        let has_else =
+            obj.else_.is_some() || Self::negated_tag_values(obj, discriminator).is_some();
+        (properties.len() == 1 && condition.const_.is_some() && has_else)
+            .then_some((discriminator, properties))
+    }
+
+    /// Get the enum values a conditional's discriminator can take when its `if` const does not match
+    ///
+    /// Only enum discriminators declared alongside the conditional have a known complement.
+    fn negated_tag_values(
+        obj: &SchemaObject,
+        discriminator: &str,
+    ) -> Option<Vec<serde_json::Value>> {
+        let JsonSchema::Object(if_obj) = obj.if_.as_deref()? else {
+            return None;
+        };
+        let JsonSchema::Object(condition) = if_obj.properties.as_ref()?.get(discriminator)? else {
+            return None;
+        };
+        let JsonSchema::Object(tag) = obj.properties.as_ref()?.get(discriminator)? else {
+            return None;
+        };
+        let values = tag.enum_.as_ref()?;
+        Some(
+            values
+                .iter()
+                .filter(|value| Some(*value) != condition.const_.as_ref())
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Merge a shared base (sibling properties or allOf) into each oneOf/anyOf branch
+    fn merge_base_into_branches(&mut self, obj: &SchemaObject) -> Result<Option<Vec<JsonSchema>>> {
+        let Some(branches) = obj.one_of.as_ref().or(obj.any_of.as_ref()) else {
+            return Ok(None);
+        };
+        if obj.properties.is_none() && obj.required.is_none() && obj.all_of.is_none() {
+            return Ok(None);
+        }
+
+        let mut base = obj.clone();
+        base.one_of = None;
+        base.any_of = None;
+        base.description = None;
+        if let Some(all_of) = &obj.all_of {
+            base = self.merge_all_of_schemas(&base, all_of)?;
+        }
+        base.type_
+            .get_or_insert(SchemaType::Single(SingleType::Object));
+
+        branches
+            .iter()
+            .map(|branch| match branch {
+                JsonSchema::Object(_) => self
+                    .merge_all_of_schemas(&base, std::slice::from_ref(branch))
+                    .map(|merged| JsonSchema::Object(Box::new(merged))),
+                JsonSchema::Boolean(_) => Ok(branch.clone()),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+
     /// Resolve reference if object is a $ref
     fn resolve_reference_if_needed<'a>(&'a self, obj: &'a SchemaObject) -> &'a SchemaObject {
         if let Some(ref_path) = &obj.ref_
             && let Some(def_name) = ref_path
                 .strip_prefix("#/$defs/")
                 .or_else(|| ref_path.strip_prefix("#/definitions/"))
-            && let Some(JsonSchema::Object(ref_obj)) = self.definitions.get(def_name)
+            && let Some(JsonSchema::Object(ref_obj)) =
+                self.definitions.get(self.scoped_definition_key(def_name))
         {
             return ref_obj;
         }
@@ -288,20 +1462,127 @@ impl SchemaConverter {
         comment: &str,
     ) -> Result<Option<String>> {
         let indent_str = Self::create_indent(indent);
-        let types: Result<Vec<_>> = schemas.iter().map(|s| self.inline_type(s)).collect();
+        let mut variants = String::new();
+        let mut types = Vec::with_capacity(schemas.len());
+        for (i, schema) in schemas.iter().enumerate() {
+            if kind != "allOf"
+                && self.options.named_union_variants
+                && Self::is_object_branch(schema)
+            {
+                let variant_name = format!("{}Variant{}", name, i + 1);
+                variants.push_str(&self.declaration_separator());
+                variants.push_str(&self.convert_schema(schema, &variant_name, indent)?);
+                types.push(variant_name);
+            } else {
+                types.push(self.inline_type(schema)?);
+            }
+        }
 
-        let separator = if kind == "allOf" { " & " } else { " | " };
+        if kind == "allOf" {
+            types = Self::simplify_intersection(types);
+        } else if let Some(supertype) = self.oversized_union(&types, name) {
+            types = vec![supertype];
+        }
+        let (combined, operator) = if kind == "allOf" {
+            (self.renderer.intersection(&types), "&")
+        } else {
+            (self.renderer.union(&types), "|")
+        };
+        let declaration =
+            self.format_combined_declaration(&indent_str, name, combined, &types, operator);
 
         self.generated_types.insert(name.to_string());
 
-        Ok(Some(format!(
-            "{}--- {}\n{}export type {} = {}",
-            indent_str,
-            comment,
+        let comment = if self.options.emit_comments {
+            format!("{}--- {}\n", indent_str, comment)
+        } else {
+            String::new()
+        };
+
+        Ok(Some(format!("{}{}{}", comment, declaration, variants)))
+    }
+
+    /// Collapse a union with more members than allowed into their common supertype
+    fn oversized_union(&mut self, members: &[String], label: &str) -> Option<String> {
+        let max = self.options.max_union_members?;
+        if members.len() <= max {
+            return None;
+        }
+        let supertype = if members.iter().all(|m| m.starts_with('"')) {
+            "string"
+        } else if members.iter().all(|m| m == "number") {
+            "number"
+        } else if members.iter().all(|m| m == "true" || m == "false") {
+            "boolean"
+        } else {
+            "any"
+        };
+        self.diagnostics.warn(format!(
+            "union for '{}' has {} members, more than the limit of {}; collapsed to {}",
+            label,
+            members.len(),
+            max,
+            supertype
+        ));
+        Some(supertype.to_string())
+    }
+
+    /// Collapse an enum with more distinct values than allowed into their common supertype
+    fn oversized_enum(&mut self, values: &[serde_json::Value], label: &str) -> Option<String> {
+        self.options.max_union_members?;
+        let mut members: Vec<String> = Vec::with_capacity(values.len());
+        for value in values {
+            let member = match value {
+                serde_json::Value::Number(_) => "number".to_string(),
+                serde_json::Value::Bool(_) | serde_json::Value::Null => value.to_string(),
+                serde_json::Value::String(s) => Self::luau_string(s),
+                _ => value.to_string(),
+            };
+            if !members.contains(&member) {
+                members.push(member);
+            }
+        }
+        self.oversized_union(&members, label)
+    }
+
+    /// Declare a union or intersection, wrapping one member per line when it is too wide
+    fn format_combined_declaration(
+        &self,
+        indent_str: &str,
+        name: &str,
+        combined: String,
+        members: &[String],
+        operator: &str,
+    ) -> String {
+        let declaration = format!(
+            "{}{} {} = {}",
             indent_str,
+            self.type_keyword(name),
             name,
-            types?.join(separator)
-        )))
+            combined
+        );
+        match self.options.union_wrap_width {
+            Some(width) if declaration.len() > width && members.len() > 1 => {
+                let mut wrapped = format!("{}{} {} =", indent_str, self.type_keyword(name), name);
+                for member in members {
+                    wrapped.push_str(&format!("\n{}    {} {}", indent_str, operator, member));
+                }
+                wrapped
+            }
+            _ => declaration,
+        }
+    }
+
+    /// Check whether a union branch is an inline object schema worth naming
+    fn is_object_branch(schema: &JsonSchema) -> bool {
+        match schema {
+            JsonSchema::Object(obj) => {
+                obj.ref_.is_none()
+                    && (obj.type_ == Some(SchemaType::Single(SingleType::Object))
+                        || (obj.type_.is_none() && obj.properties.is_some()))
+            }
+            JsonSchema::Boolean(_) => false,
+        }
     }
 
     /// Handle type-specific conversion logic
@@ -314,8 +1595,15 @@ impl SchemaConverter {
         let indent_str = Self::create_indent(indent);
         self.generated_types.insert(name.to_string());
 
-        if let Some(type_) = &obj.type_ {
-            let types = Self::get_single_types(type_);
+        if let Some(types) = Self::effective_types(obj) {
+            if types.is_empty() {
+                return Ok(format!(
+                    "{}{} {} = never",
+                    indent_str,
+                    self.type_keyword(name),
+                    name
+                ));
+            }
 
             // Handle union types (multiple types)
             if types.len() > 1 {
@@ -325,11 +1613,19 @@ impl SchemaConverter {
             // Handle single type
             let single_type = types[0];
             self.generate_single_type(obj, name, single_type, indent)
-        } else if obj.properties.is_some() || obj.additional_properties.is_some() {
+        } else if obj.properties.is_some()
+            || obj.additional_properties.is_some()
+            || obj.pattern_properties.is_some()
+        {
             // Infer as object if properties exist
             self.generate_object_type(obj, name, indent)
         } else {
-            Ok(format!("{}export type {} = any", indent_str, name))
+            Ok(format!(
+                "{}{} {} = any",
+                indent_str,
+                self.type_keyword(name),
+                name
+            ))
         }
     }
 
@@ -341,17 +1637,44 @@ impl SchemaConverter {
         types: &[&SingleType],
         indent_str: &str,
     ) -> Result<String> {
-        let type_strings = self.map_types_to_strings(types);
+        let type_strings = self.map_types_to_strings(obj, types)?;
         let constraints = self
             .format_constraints_with_indent(&JsonSchema::Object(Box::new(obj.clone())), indent_str);
 
-        Ok(format!(
-            "{}{}export type {} = {}",
-            constraints,
-            indent_str,
-            name,
-            type_strings.join(" | ")
-        ))
+        if let Some(nullable) = self.nullable_type(obj, &type_strings) {
+            return Ok(format!(
+                "{}{}{} {} = {}",
+                constraints,
+                indent_str,
+                self.type_keyword(name),
+                name,
+                nullable
+            ));
+        }
+
+        let combined = self.renderer.union(&type_strings);
+        let declaration =
+            self.format_combined_declaration(indent_str, name, combined, &type_strings, "|");
+        Ok(format!("{}{}", constraints, declaration))
+    }
+
+    /// Write a union containing `nil` as `T?` when the nullable style asks for it
+    fn nullable_type(&self, obj: &SchemaObject, type_strings: &[String]) -> Option<String> {
+        if self.options.nullable_style != NullableStyle::Question {
+            return None;
+        }
+        let nil = self.renderer.scalar(obj, "nil");
+        let others: Vec<_> = type_strings
+            .iter()
+            .filter(|t| **t != nil)
+            .cloned()
+            .collect();
+        match others.len() {
+            n if n == type_strings.len() => None,
+            0 => None,
+            1 => Some(format!("{}?", others[0])),
+            _ => Some(format!("({})?", self.renderer.union(&others))),
+        }
     }
 
     /// Generate type for a single schema type
@@ -379,28 +1702,88 @@ impl SchemaConverter {
                 );
 
                 Ok(format!(
-                    "{}{}export type {} = {}",
-                    constraints, indent_str, name, type_name
+                    "{}{}{} {} = {}",
+                    constraints,
+                    indent_str,
+                    self.type_keyword(name),
+                    name,
+                    self.renderer.scalar(obj, type_name)
                 ))
             }
-            SingleType::Boolean => Ok(format!("{}export type {} = boolean", indent_str, name)),
-            SingleType::Null => Ok(format!("{}export type {} = nil", indent_str, name)),
+            SingleType::Boolean => Ok(format!(
+                "{}{} {} = {}",
+                indent_str,
+                self.type_keyword(name),
+                name,
+                self.renderer.scalar(obj, "boolean")
+            )),
+            SingleType::Null => Ok(format!(
+                "{}{} {} = {}",
+                indent_str,
+                self.type_keyword(name),
+                name,
+                self.renderer.scalar(obj, "nil")
+            )),
         }
     }
 
     /// Map SingleType variants to their string representations
-    fn map_types_to_strings(&self, types: &[&SingleType]) -> Vec<String> {
-        types
-            .iter()
-            .map(|t| match t {
-                SingleType::String => "string".to_string(),
-                SingleType::Number | SingleType::Integer => "number".to_string(),
-                SingleType::Boolean => "boolean".to_string(),
-                SingleType::Null => "nil".to_string(),
-                SingleType::Array => "{ any }".to_string(),
+    fn map_types_to_strings(
+        &mut self,
+        obj: &SchemaObject,
+        types: &[&SingleType],
+    ) -> Result<Vec<String>> {
+        let mut type_strings: Vec<String> = Vec::with_capacity(types.len());
+        for t in types {
+            let type_string = match t {
+                SingleType::String => self.renderer.scalar(obj, "string"),
+                SingleType::Number | SingleType::Integer => self.renderer.scalar(obj, "number"),
+                SingleType::Boolean => self.renderer.scalar(obj, "boolean"),
+                SingleType::Null => self.renderer.scalar(obj, "nil"),
+                SingleType::Array => format!("{{ {} }}", self.array_item_type(obj)?),
                 SingleType::Object => "{ [string]: any }".to_string(),
-            })
-            .collect()
+            };
+            // `integer` and `number` both map to `number`, so keep only the first
+            if !type_strings.contains(&type_string) {
+                type_strings.push(type_string);
+            }
+        }
+        Ok(type_strings)
+    }
+
+    /// Get the only property of an object that wraps a single required value
+    ///
+    /// Only applies with `unwrap_single_property`, to `object` schemas with no other keys allowed.
+    fn unwrapped_property<'a>(
+        &self,
+        obj: &'a SchemaObject,
+    ) -> Option<(&'a String, &'a JsonSchema)> {
+        if !self.options.unwrap_single_property
+            || !matches!(
+                Self::effective_types(obj).as_deref(),
+                Some([SingleType::Object])
+            )
+            || obj.ref_.is_some()
+            || obj.enum_.is_some()
+            || obj.const_.is_some()
+            || obj.all_of.is_some()
+            || obj.any_of.is_some()
+            || obj.one_of.is_some()
+            || obj.pattern_properties.is_some()
+            || !matches!(
+                obj.additional_properties,
+                None | Some(AdditionalProperties::Boolean(false))
+            )
+        {
+            return None;
+        }
+        let properties = obj.properties.as_ref()?;
+        let (key, inner) = properties.iter().next().filter(|_| properties.len() == 1)?;
+        obj.required
+            .iter()
+            .flatten()
+            .any(|required| required == key)
+            .then_some((key, inner))
     }
 
     /// Generate object type definition
@@ -411,18 +1794,48 @@ impl SchemaConverter {
         indent: usize,
     ) -> Result<String> {
         let indent_str = Self::create_indent(indent);
-        let mut output = format!("{}export type {} = {{\n", indent_str, name);
 
-        // Handle properties
-        if let Some(properties) = &obj.properties {
-            self.generate_properties(obj, properties, indent, &mut output)?;
+        // A single-key wrapper object is declared as its value type when enabled
+        if let Some((key, inner)) = self.unwrapped_property(obj) {
+            let inner_type = self.inline_type(inner)?;
+            let comment = if self.options.emit_comments {
+                format!("{}--- @unwrapped {}\n", indent_str, Self::luau_key(key))
+            } else {
+                String::new()
+            };
+            return Ok(format!(
+                "{}{}{} {} = {}",
+                comment,
+                indent_str,
+                self.type_keyword(name),
+                name,
+                inner_type
+            ));
+        }
+
+        let mut body = String::from("{\n");
+        self.count(|stats| &mut stats.object_types, 1);
+
+        // Handle properties, including patternProperties that match a single literal key
+        let mut properties = obj.properties.clone().unwrap_or_default();
+        Self::add_literal_pattern_properties(obj, &mut properties);
+        self.add_missing_required(obj, &mut properties)?;
+        let has_index = Self::has_string_index(obj);
+        if !properties.is_empty() {
+            self.generate_properties(obj, &properties, indent, !has_index, &mut body)?;
         }
 
-        // Handle additionalProperties
-        self.generate_additional_properties(obj, indent, &mut output)?;
+        // Handle additionalProperties and wildcard patternProperties
+        self.generate_additional_properties(obj, indent, &mut body)?;
 
-        output.push_str(&format!("{}}}", indent_str));
-        Ok(output)
+        body.push_str(&format!("{}}}", indent_str));
+        Ok(format!(
+            "{}{} {} = {}",
+            indent_str,
+            self.type_keyword(name),
+            name,
+            self.renderer.object(obj, &body)
+        ))
     }
 
     /// Generate properties for object type
@@ -431,6 +1844,7 @@ impl SchemaConverter {
         obj: &SchemaObject,
         properties: &HashMap<String, JsonSchema>,
         indent: usize,
+        ends_body: bool,
         output: &mut String,
     ) -> Result<()> {
         let indent_str = Self::create_indent(indent);
@@ -440,16 +1854,17 @@ impl SchemaConverter {
             .map(|r| r.iter().cloned().collect())
             .unwrap_or_default();
 
-        let mut prop_names: Vec<_> = properties.keys().cloned().collect();
-        prop_names.sort();
+        let prop_names = self.ordered_property_names(properties, &required_fields);
 
-        for prop_name in prop_names {
-            if let Some(prop_schema) = properties.get(&prop_name) {
+        for (i, prop_name) in prop_names.iter().enumerate() {
+            if let Some(prop_schema) = properties.get(prop_name) {
+                let separator = self.field_separator(ends_body && i + 1 == prop_names.len());
                 self.generate_property(
                     prop_schema,
-                    &prop_name,
-                    required_fields.contains(&prop_name),
+                    prop_name,
+                    required_fields.contains(prop_name),
                     &indent_str,
+                    separator,
                     output,
                 )?;
             }
@@ -458,6 +1873,44 @@ impl SchemaConverter {
         Ok(())
     }
 
+    /// Order property names for output according to the property order option
+    fn ordered_property_names(
+        &self,
+        properties: &HashMap<String, JsonSchema>,
+        required_fields: &HashSet<String>,
+    ) -> Vec<String> {
+        let mut prop_names: Vec<_> = properties.keys().cloned().collect();
+        prop_names.sort();
+        if self.options.property_order == PropertyOrder::RequiredFirst {
+            // Stable sort keeps each group alphabetical
+            prop_names.sort_by_key(|name| !required_fields.contains(name));
+        }
+        prop_names
+    }
+
+    /// Handle required keys that have no property schema
+    ///
+    /// Strict mode rejects them, otherwise they are emitted as `any`.
+    fn add_missing_required(
+        &self,
+        obj: &SchemaObject,
+        properties: &mut HashMap<String, JsonSchema>,
+    ) -> Result<()> {
+        for required in obj.required.iter().flatten() {
+            if properties.contains_key(required) {
+                continue;
+            }
+            if self.options.strict {
+                return Err(ConversionError::InvalidSchema(format!(
+                    "required property '{}' is not defined in properties",
+                    required
+                )));
+            }
+            properties.insert(required.clone(), JsonSchema::Boolean(true));
+        }
+        Ok(())
+    }
+
     /// Generate individual property
     fn generate_property(
         &mut self,
@@ -465,16 +1918,28 @@ impl SchemaConverter {
         prop_name: &str,
         is_required: bool,
         indent_str: &str,
+        separator: &str,
         output: &mut String,
     ) -> Result<()> {
         // Add property description
         if let JsonSchema::Object(prop_obj) = prop_schema
             && let Some(desc) = &prop_obj.description
+            && self.options.emit_comments
+            && self.options.emit_property_descriptions
         {
             output.push_str(&format!("{}    --- {}\n", indent_str, desc));
         }
+        if let JsonSchema::Object(prop_obj) = prop_schema {
+            output.push_str(&self.format_extensions(prop_obj, &format!("{}    ", indent_str)));
+            let examples =
+                self.format_examples(prop_obj, prop_name, &format!("{}    ", indent_str));
+            output.push_str(&examples);
+            let not_comment = self.format_not_comment(prop_obj, &format!("{}    ", indent_str))?;
+            output.push_str(&not_comment);
+        }
 
-        let prop_type = self.inline_type(prop_schema)?;
+        let (prop_type, refs) = self.inline_type_with_refs(prop_schema)?;
+        output.push_str(&self.format_unresolved_refs(&refs, &format!("{}    ", indent_str)));
         let constraints =
             self.format_constraints_with_indent(prop_schema, &format!("{}    ", indent_str));
         if !constraints.is_empty() {
@@ -493,15 +1958,67 @@ impl SchemaConverter {
             }
         }
 
-        let optional_marker = if is_required { "" } else { "?" };
+        // Distinguish schema-optional fields from explicitly nullable ones
+        if !is_required && self.options.emit_optional_tag {
+            output.push_str(&format!("{}    --- @optional\n", indent_str));
+        }
+
+        let optional_marker = self.optional_marker(&prop_type, is_required);
         output.push_str(&format!(
-            "{}    {}: {}{},\n",
-            indent_str, prop_name, prop_type, optional_marker
+            "{}    {}: {}{}{}\n",
+            indent_str,
+            Self::luau_key(prop_name),
+            prop_type,
+            optional_marker,
+            separator
         ));
 
         Ok(())
     }
 
+    /// Get the separator written after a multi-line field, left off the last one when the style asks for it
+    fn field_separator(&self, is_last: bool) -> &'static str {
+        if is_last && !self.options.trailing_separator {
+            ""
+        } else {
+            self.options.field_separator.as_str()
+        }
+    }
+
+    /// Check whether an object gets a `[string]` index from additionalProperties or wildcard patterns
+    fn has_string_index(obj: &SchemaObject) -> bool {
+        matches!(
+            obj.additional_properties,
+            Some(AdditionalProperties::Boolean(true) | AdditionalProperties::Schema(_))
+        ) || !Self::wildcard_patterns(obj).is_empty()
+    }
+
+    /// Get the `?` or ` | nil` written after an optional field's type, following the nullable style
+    ///
+    /// `any` already admits `nil`, so the marker is left off when `mark_optional_any` is disabled.
+    fn optional_marker(&self, prop_type: &str, is_required: bool) -> &'static str {
+        if is_required
+            || prop_type.ends_with('?')
+            || (prop_type == "any" && !self.options.mark_optional_any)
+        {
+            return "";
+        }
+        match self.options.nullable_style {
+            NullableStyle::Question => "?",
+            NullableStyle::UnionNil if Self::admits_nil(prop_type) => "",
+            NullableStyle::UnionNil => " | nil",
+        }
+    }
+
+    /// Check whether a type is `nil` or a union with a `nil` member, parenthesized or not
+    fn admits_nil(prop_type: &str) -> bool {
+        let members = prop_type
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+            .unwrap_or(prop_type);
+        members.split(" | ").any(|member| member == "nil")
+    }
+
     /// Generate additional properties definition
     fn generate_additional_properties(
         &mut self,
@@ -510,10 +2027,12 @@ impl SchemaConverter {
         output: &mut String,
     ) -> Result<()> {
         let indent_str = Self::create_indent(indent);
+        let mut index_types = Vec::new();
+
         if let Some(additional) = &obj.additional_properties {
-            let add_type = match additional {
-                AdditionalProperties::Boolean(true) => "any".to_string(),
-                AdditionalProperties::Boolean(false) => return Ok(()), // No additional properties allowed
+            match additional {
+                AdditionalProperties::Boolean(true) => index_types.push("any".to_string()),
+                AdditionalProperties::Boolean(false) => {} // No additional properties allowed
                 AdditionalProperties::Schema(schema) => {
                     // Add format constraints for additional properties if they exist
                     if let JsonSchema::Object(_) = schema.as_ref() {
@@ -523,14 +2042,77 @@ impl SchemaConverter {
                             output.push_str(&constraints);
                         }
                     }
-                    self.inline_type(schema)?
+                    index_types.push(self.inline_type(schema)?);
                 }
+            }
+        }
+
+        // Wildcard patternProperties share the single string index
+        for (pattern, pattern_schema) in Self::wildcard_patterns(obj) {
+            if self.options.emit_comments && self.options.emit_constraints {
+                output.push_str(&format!(
+                    "{}    --- @patternProperty {}\n",
+                    indent_str,
+                    self.format_pattern(pattern)
+                ));
+            }
+            let pattern_type = self.inline_type(pattern_schema)?;
+            if !index_types.contains(&pattern_type) {
+                index_types.push(pattern_type);
+            }
+        }
+
+        if !index_types.is_empty() {
+            let index_type = if index_types.iter().any(|t| t == "any") {
+                "any".to_string()
+            } else {
+                self.renderer.union(&index_types)
             };
-            output.push_str(&format!("{}    [string]: {},\n", indent_str, add_type));
+            output.push_str(&format!(
+                "{}    [string]: {}{}\n",
+                indent_str,
+                index_type,
+                self.field_separator(true)
+            ));
         }
         Ok(())
     }
 
+    /// Add patternProperties matching a single literal key as ordinary properties
+    fn add_literal_pattern_properties(
+        obj: &SchemaObject,
+        properties: &mut HashMap<String, JsonSchema>,
+    ) {
+        for (pattern, pattern_schema) in obj.pattern_properties.iter().flatten() {
+            if let Some(key) = Self::literal_pattern_key(pattern) {
+                properties
+                    .entry(key.to_string())
+                    .or_insert_with(|| pattern_schema.clone());
+            }
+        }
+    }
+
+    /// Get the patternProperties that do not match a single literal key
+    ///
+    /// Sorted by pattern so comments and index members do not follow `HashMap` iteration order.
+    fn wildcard_patterns(obj: &SchemaObject) -> Vec<(&String, &JsonSchema)> {
+        let mut patterns: Vec<_> = obj
+            .pattern_properties
+            .iter()
+            .flatten()
+            .filter(|(pattern, _)| Self::literal_pattern_key(pattern).is_none())
+            .collect();
+        patterns.sort_by(|a, b| a.0.cmp(b.0));
+        patterns
+    }
+
+    /// Get the key matched by a literal anchored pattern such as `^id$`
+    fn literal_pattern_key(pattern: &str) -> Option<&str> {
+        let key = pattern.strip_prefix('^')?.strip_suffix('$')?;
+        let is_literal = !key.is_empty() && !key.chars().any(|c| "\\.^$|?*+()[]{}".contains(c));
+        is_literal.then_some(key)
+    }
+
     /// Generate array type definition
     fn generate_array_type(
         &mut self,
@@ -539,23 +2121,113 @@ impl SchemaConverter {
         indent: usize,
     ) -> Result<String> {
         let indent_str = Self::create_indent(indent);
-        let item_type = if let Some(items) = &obj.items {
-            self.inline_type(items)?
-        } else {
-            "any".to_string()
-        };
+        let item_type = self.array_item_type(obj)?;
 
         let constraints = self.format_constraints_with_indent(
             &JsonSchema::Object(Box::new(obj.clone())),
             &indent_str,
         );
 
+        // Put long element types on their own line
+        if let Some(threshold) = self.options.multiline_array_threshold
+            && item_type.len() > threshold
+        {
+            return Ok(format!(
+                "{}{}{} {} = {{\n{}    {}\n{}}}",
+                constraints,
+                indent_str,
+                self.type_keyword(name),
+                name,
+                indent_str,
+                item_type,
+                indent_str
+            ));
+        }
+
         Ok(format!(
-            "{}{}export type {} = {{ {} }}",
-            constraints, indent_str, name, item_type
+            "{}{}{} {} = {{ {} }}",
+            constraints,
+            indent_str,
+            self.type_keyword(name),
+            name,
+            item_type
         ))
     }
 
+    /// Get the element type of an array, optionally falling back to `contains`
+    fn array_item_type(&mut self, obj: &SchemaObject) -> Result<String> {
+        self.count(|stats| &mut stats.array_types, 1);
+        if let Some((tuple_items, rest)) = Self::tuple_items(obj) {
+            // Tuples are typed by the union of their positions and any later elements
+            let mut positions = self.tuple_member_types(tuple_items)?;
+            self.tuple_members = Some((tuple_items.to_vec(), positions.clone()));
+            if let Some(rest) = rest {
+                positions.push(self.inline_type(rest)?);
+            }
+            let mut members: Vec<String> = Vec::with_capacity(positions.len());
+            for member in positions {
+                if member != "never" && !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+            return Ok(match members.as_slice() {
+                [] => "never".to_string(),
+                [single] => single.clone(),
+                _ => self.renderer.union(&members),
+            });
+        }
+        if let Some(Items::Single(items)) = &obj.items {
+            self.inline_type(items)
+        } else if let Some(contains) = &obj.contains
+            && self.options.contains_as_items
+        {
+            self.inline_type(contains)
+        } else {
+            Ok("any".to_string())
+        }
+    }
+
+    /// Get a tuple's positional schemas and the schema for any later elements
+    ///
+    /// Positions come from `prefixItems`, followed by `items`, or from the legacy array
+    /// form of `items`, followed by `additionalItems`.
+    fn tuple_items(obj: &SchemaObject) -> Option<(&[JsonSchema], Option<&JsonSchema>)> {
+        match (&obj.prefix_items, &obj.items) {
+            (Some(prefix_items), items) if !prefix_items.is_empty() => {
+                let rest = match items {
+                    Some(Items::Single(rest)) => Some(rest.as_ref()),
+                    _ => None,
+                };
+                Some((prefix_items, rest))
+            }
+            (_, Some(Items::Tuple(positions))) if !positions.is_empty() => {
+                Some((positions, obj.additional_items.as_deref()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the inline type of each tuple position
+    fn tuple_member_types(&mut self, prefix_items: &[JsonSchema]) -> Result<Vec<String>> {
+        prefix_items.iter().map(|s| self.inline_type(s)).collect()
+    }
+
+    /// Generate a `@tuple` comment listing the position types the element type was built from
+    fn add_tuple_comment(&mut self, obj: &SchemaObject, indent_str: &str, output: &mut String) {
+        let Some((tuple_items, _)) = Self::tuple_items(obj) else {
+            return;
+        };
+        if let Some((positions, members)) = self.tuple_members.take()
+            && positions == tuple_items
+        {
+            output.push_str(&format!(
+                "{}--- @tuple [{}]\n",
+                indent_str,
+                members.join(", ")
+            ));
+        }
+    }
+
     /// Generate enum type definition
     fn generate_enum_type(
         &mut self,
@@ -564,8 +2236,18 @@ impl SchemaConverter {
         indent_str: &str,
     ) -> Result<String> {
         self.generated_types.insert(name.to_string());
-        let union = self.convert_enum(values);
-        Ok(format!("{}export type {} = {}", indent_str, name, union))
+        self.count(|stats| &mut stats.enum_types, 1);
+        let union = match self.oversized_enum(values, name) {
+            Some(supertype) => supertype,
+            None => self.convert_enum(values),
+        };
+        Ok(format!(
+            "{}{} {} = {}",
+            indent_str,
+            self.type_keyword(name),
+            name,
+            union
+        ))
     }
 
     /// Generate const type definition
@@ -576,16 +2258,58 @@ impl SchemaConverter {
         indent_str: &str,
     ) -> Result<String> {
         self.generated_types.insert(name.to_string());
+        self.count(|stats| &mut stats.const_types, 1);
         let literal = self.convert_const(value);
-        Ok(format!("{}export type {} = {}", indent_str, name, literal))
+        Ok(format!(
+            "{}{} {} = {}",
+            indent_str,
+            self.type_keyword(name),
+            name,
+            literal
+        ))
     }
 
     /// Method for inline type
     fn inline_type(&mut self, schema: &JsonSchema) -> Result<String> {
-        match schema {
+        self.enter_nested()?;
+        let result = match schema {
             JsonSchema::Boolean(true) => Ok("any".to_string()),
             JsonSchema::Boolean(false) => Ok("never".to_string()),
             JsonSchema::Object(obj) => self.inline_object_type(obj),
+        };
+        self.depth -= 1;
+        result
+    }
+
+    /// Join inline union members, collapsing oversized unions and writing `nil` members as `T?`
+    fn inline_union(&mut self, obj: &SchemaObject, types: Vec<String>) -> Result<String> {
+        if let Some(supertype) = self.oversized_union(&types, "inline union") {
+            return Ok(supertype);
+        }
+        if let Some(nullable) = self.nullable_type(obj, &types) {
+            return Ok(nullable);
+        }
+        Ok(format!("({})", self.renderer.union(&types)))
+    }
+
+    /// Join inline intersection members, dropping redundant `any` members
+    fn inline_intersection(&self, types: Vec<String>) -> String {
+        match Self::simplify_intersection(types).as_slice() {
+            [single] => single.clone(),
+            types => format!("({})", self.renderer.intersection(types)),
+        }
+    }
+
+    /// Drop `any` members of an intersection, collapsing it to `never` when one is present
+    fn simplify_intersection(types: Vec<String>) -> Vec<String> {
+        if types.iter().any(|t| t == "never") {
+            return vec!["never".to_string()];
+        }
+        let narrowed: Vec<_> = types.into_iter().filter(|t| t != "any").collect();
+        if narrowed.is_empty() {
+            vec!["any".to_string()]
+        } else {
+            narrowed
         }
     }
 
@@ -595,12 +2319,28 @@ impl SchemaConverter {
         if let Some(ref_path) = &obj.ref_ {
             return self.resolve_ref(ref_path);
         }
+        if let Some(builtin) = obj.format.as_deref().and_then(|f| self.roblox_type(f)) {
+            return Ok(builtin.to_string());
+        }
 
         // Handle enum and const
         if let Some(enum_values) = &obj.enum_ {
-            return Ok(self.convert_enum(enum_values));
+            self.count(|stats| &mut stats.enum_types, 1);
+            if let Some(supertype) = self.oversized_enum(enum_values, "enum") {
+                return Ok(supertype);
+            }
+            let union = self.convert_enum(enum_values);
+            if let Some((_, name)) = self
+                .shared_enums
+                .iter()
+                .find(|(shared, _)| *shared == union)
+            {
+                return Ok(name.clone());
+            }
+            return Ok(union);
         }
         if let Some(const_value) = &obj.const_ {
+            self.count(|stats| &mut stats.const_types, 1);
             return Ok(self.convert_const(const_value));
         }
 
@@ -615,14 +2355,28 @@ impl SchemaConverter {
 
     /// Method for inline composition types
     fn inline_composition_types(&mut self, obj: &SchemaObject) -> Result<Option<String>> {
+        if let Some((_, conditional)) = self.conditional_as_one_of(obj)? {
+            return self.inline_composition_types(&conditional);
+        }
         // For brevity, keeping the original implementation here
+        if let Some(branches) = self.merge_base_into_branches(obj)? {
+            let types: Result<Vec<_>> = branches.iter().map(|s| self.inline_type(s)).collect();
+            return self.inline_union(obj, types?).map(Some);
+        }
+        if let Some(values) = Self::const_branch_values(obj) {
+            self.count(|stats| &mut stats.enum_types, 1);
+            if let Some(supertype) = self.oversized_enum(&values, "enum") {
+                return Ok(Some(supertype));
+            }
+            return Ok(Some(self.convert_enum(&values)));
+        }
         if let Some(any_of) = &obj.any_of {
             let types: Result<Vec<_>> = any_of.iter().map(|s| self.inline_type(s)).collect();
-            return Ok(Some(format!("({})", types?.join(" | "))));
+            return self.inline_union(obj, types?).map(Some);
         }
         if let Some(one_of) = &obj.one_of {
             let types: Result<Vec<_>> = one_of.iter().map(|s| self.inline_type(s)).collect();
-            return Ok(Some(format!("({})", types?.join(" | "))));
+            return self.inline_union(obj, types?).map(Some);
         }
         if let Some(all_of) = &obj.all_of {
             let parent_has_props = obj.properties.is_some()
@@ -661,41 +2415,49 @@ impl SchemaConverter {
                 } else {
                     let mut parts = vec![merged_part];
                     parts.extend(ref_types);
-                    return Ok(Some(format!("({})", parts.join(" & "))));
+                    return Ok(Some(self.inline_intersection(parts)));
                 }
             }
 
             let types: Result<Vec<_>> = all_of.iter().map(|s| self.inline_type(s)).collect();
-            return Ok(Some(format!("({})", types?.join(" & "))));
+            return Ok(Some(self.inline_intersection(types?)));
         }
         Ok(None)
     }
 
     /// Method for inline type specific
     fn inline_type_specific(&mut self, obj: &SchemaObject) -> Result<String> {
-        if let Some(type_) = &obj.type_ {
-            let types = Self::get_single_types(type_);
+        if let Some(types) = Self::effective_types(obj) {
+            if types.is_empty() {
+                return Ok("never".to_string());
+            }
 
             if types.len() > 1 {
-                let type_strings = self.map_types_to_strings(&types);
-                return Ok(format!("({})", type_strings.join(" | ")));
+                let type_strings = self.map_types_to_strings(obj, &types)?;
+                if type_strings.len() == 1 {
+                    return Ok(type_strings[0].clone());
+                }
+
+                if let Some(nullable) = self.nullable_type(obj, &type_strings) {
+                    return Ok(nullable);
+                }
+                return Ok(format!("({})", self.renderer.union(&type_strings)));
             }
 
             let single_type = types[0];
             match single_type {
-                SingleType::String => Ok("string".to_string()),
-                SingleType::Number | SingleType::Integer => Ok("number".to_string()),
-                SingleType::Boolean => Ok("boolean".to_string()),
-                SingleType::Null => Ok("nil".to_string()),
-                SingleType::Array => {
-                    let item_type = if let Some(items) = &obj.items {
-                        self.inline_type(items)?
-                    } else {
-                        "any".to_string()
-                    };
-                    Ok(format!("{{ {} }}", item_type))
+                SingleType::String => Ok(self.renderer.scalar(obj, "string")),
+                SingleType::Number | SingleType::Integer => Ok(self.renderer.scalar(obj, "number")),
+                SingleType::Boolean => Ok(self.renderer.scalar(obj, "boolean")),
+                SingleType::Null => Ok(self.renderer.scalar(obj, "nil")),
+                SingleType::Array => Ok(format!("{{ {} }}", self.array_item_type(obj)?)),
+                SingleType::Object => {
+                    if let Some((_, inner)) = self.unwrapped_property(obj) {
+                        return self.inline_type(inner);
+                    }
+                    let inline = self.inline_object_properties(obj)?;
+                    Ok(self.renderer.object(obj, &inline))
                 }
-                SingleType::Object => self.inline_object_properties(obj),
             }
         } else if obj.properties.is_some() {
             Ok("{ [string]: any }".to_string())
@@ -706,7 +2468,35 @@ impl SchemaConverter {
 
     /// Method for inline object properties
     fn inline_object_properties(&mut self, obj: &SchemaObject) -> Result<String> {
-        if let Some(properties) = &obj.properties {
+        self.count(|stats| &mut stats.object_types, 1);
+        let mut properties = obj.properties.clone().unwrap_or_default();
+        Self::add_literal_pattern_properties(obj, &mut properties);
+        self.add_missing_required(obj, &mut properties)?;
+
+        // Extra keys and wildcard patternProperties share one string index
+        let mut index_types = Vec::new();
+        match &obj.additional_properties {
+            Some(AdditionalProperties::Boolean(true)) => index_types.push("any".to_string()),
+            Some(AdditionalProperties::Schema(schema)) => {
+                index_types.push(self.inline_type(schema)?)
+            }
+            _ => {}
+        }
+        for (_, pattern_schema) in Self::wildcard_patterns(obj) {
+            let pattern_type = self.inline_type(pattern_schema)?;
+            if !index_types.contains(&pattern_type) {
+                index_types.push(pattern_type);
+            }
+        }
+        let index_type = if index_types.is_empty() {
+            None
+        } else if index_types.iter().any(|t| t == "any") {
+            Some("any".to_string())
+        } else {
+            Some(self.renderer.union(&index_types))
+        };
+
+        if obj.properties.is_some() || !properties.is_empty() {
             let mut inline = String::from("{ ");
             let required_fields: HashSet<_> = obj
                 .required
@@ -714,30 +2504,40 @@ impl SchemaConverter {
                 .map(|r| r.iter().cloned().collect())
                 .unwrap_or_default();
 
-            let mut prop_names: Vec<_> = properties.keys().cloned().collect();
-            prop_names.sort();
+            let prop_names = self.ordered_property_names(&properties, &required_fields);
 
             for (i, prop_name) in prop_names.iter().enumerate() {
                 if let Some(prop_schema) = properties.get(prop_name) {
                     let is_required = required_fields.contains(prop_name);
-                    let optional_marker = if is_required { "" } else { "?" };
                     let prop_type = self.inline_type(prop_schema)?;
+                    let optional_marker = self.optional_marker(&prop_type, is_required);
 
                     if i > 0 {
-                        inline.push_str(", ");
+                        inline.push_str(self.options.field_separator.as_str());
+                        inline.push(' ');
                     }
-                    inline.push_str(&format!("{}: {}{}", prop_name, prop_type, optional_marker));
+                    inline.push_str(&format!(
+                        "{}: {}{}",
+                        Self::luau_key(prop_name),
+                        prop_type,
+                        optional_marker
+                    ));
                 }
             }
+            // Extra keys follow the declared fields as a string index
+            if let Some(index_type) = index_type {
+                if !prop_names.is_empty() {
+                    inline.push_str(self.options.field_separator.as_str());
+                    inline.push(' ');
+                }
+                inline.push_str(&format!("[string]: {}", index_type));
+            }
             inline.push_str(" }");
             Ok(inline)
-        } else if let Some(additional) = &obj.additional_properties {
-            let add_type = match additional {
-                AdditionalProperties::Boolean(true) => "any".to_string(),
-                AdditionalProperties::Boolean(false) => return Ok("{ }".to_string()),
-                AdditionalProperties::Schema(schema) => self.inline_type(schema)?,
-            };
-            Ok(format!("{{ [string]: {} }}", add_type))
+        } else if let Some(index_type) = index_type {
+            Ok(format!("{{ [string]: {} }}", index_type))
+        } else if let Some(AdditionalProperties::Boolean(false)) = &obj.additional_properties {
+            Ok("{ }".to_string())
         } else {
             Ok("{ [string]: any }".to_string())
         }
@@ -745,58 +2545,400 @@ impl SchemaConverter {
 
     /// Method for converting enum values
     fn convert_enum(&self, values: &[serde_json::Value]) -> String {
-        let (all_strings, all_numbers) =
-            values
-                .iter()
-                .fold((true, true), |(strings, numbers), v| match v {
-                    serde_json::Value::String(_) => (strings, false),
-                    serde_json::Value::Number(_) => (false, numbers),
-                    serde_json::Value::Bool(_) | serde_json::Value::Null => (false, false),
-                    _ => (false, false),
-                });
-
-        if all_numbers {
-            return "number".to_string();
+        // Each value becomes its own literal type, keeping the first of any repeated members.
+        // Luau has no number singleton types, so numeric values widen to one `number` member.
+        let mut members: Vec<String> = Vec::with_capacity(values.len());
+        for value in values {
+            let member = self.convert_const(value);
+            if !members.contains(&member) {
+                members.push(member);
+            }
         }
-
-        if all_strings {
-            let parts: Vec<_> = values
-                .iter()
-                .map(|v| match v {
-                    serde_json::Value::String(s) => format!("\"{}\"", s),
-                    _ => unreachable!(),
-                })
-                .collect();
-            return parts.join(" | ");
+        if members.is_empty() {
+            return "never".to_string();
         }
 
-        "string | number | boolean | nil".to_string()
+        // A nil member is written as `T?` when the nullable style asks for it
+        if self.options.nullable_style == NullableStyle::Question
+            && members.len() > 1
+            && members.iter().any(|m| m == "nil")
+        {
+            let others: Vec<_> = members.iter().filter(|m| *m != "nil").cloned().collect();
+            return match others.as_slice() {
+                [single] => format!("{}?", single),
+                _ => format!("({})?", others.join(" | ")),
+            };
+        }
+        members.join(" | ")
     }
 
     /// Method for converting const values
     fn convert_const(&self, value: &serde_json::Value) -> String {
         match value {
-            serde_json::Value::String(s) => format!("\"{}\"", s),
             serde_json::Value::Number(_) => "number".to_string(),
-            serde_json::Value::Bool(b) => b.to_string(),
-            serde_json::Value::Null => "nil".to_string(),
-            _ => "any".to_string(),
+            serde_json::Value::String(_) | serde_json::Value::Bool(_) | serde_json::Value::Null => {
+                json_value_to_luau(value)
+            }
+            serde_json::Value::Array(items) if items.is_empty() => "{ any }".to_string(),
+            serde_json::Value::Array(items) => {
+                let mut members: Vec<String> = Vec::with_capacity(items.len());
+                for item in items {
+                    let literal = self.convert_const(item);
+                    if !members.contains(&literal) {
+                        members.push(literal);
+                    }
+                }
+                format!("{{ {} }}", members.join(" | "))
+            }
+            serde_json::Value::Object(fields) => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", Self::luau_key(k), self.convert_const(v)))
+                    .collect();
+                format!("{{ {} }}", fields.join(", "))
+            }
         }
     }
 
     /// Method for resolving $ref paths
-    fn resolve_ref(&self, ref_path: &str) -> Result<String> {
-        if let Some(def_name) = ref_path.strip_prefix("#/definitions/") {
-            return Ok(def_name.to_case(Case::Pascal));
+    fn resolve_ref(&mut self, ref_path: &str) -> Result<String> {
+        // The whole document refers back to the root type
+        if ref_path == "#" {
+            self.count(|stats| &mut stats.refs_resolved, 1);
+            if self.options.inline_all {
+                return Ok("any".to_string());
+            }
+            // Without a root declaration there is nothing to name
+            if self.options.definitions_only {
+                self.diagnostics.warn(
+                    "$ref '#' converted to any because the root type is not emitted".to_string(),
+                );
+                return Ok("any".to_string());
+            }
+            return Ok(self.renderer.reference(ref_path, &self.root_name));
+        }
+
+        if matches!(ref_path.trim_end_matches('/'), "#/definitions" | "#/$defs") {
+            return Err(ConversionError::InvalidSchema(format!(
+                "$ref '{}' points at the definitions map, not a schema",
+                ref_path
+            )));
         }
-        if let Some(def_name) = ref_path.strip_prefix("#/$defs/") {
-            return Ok(def_name.to_case(Case::Pascal));
+
+        // Pointers below the definitions name a top-level or nested definition by its key
+        let def_name = ref_path
+            .strip_prefix("#/definitions/")
+            .or_else(|| ref_path.strip_prefix("#/$defs/"));
+        if let Some(def_name) = def_name {
+            let def_name = &self.scoped_definition_key(def_name).to_string();
+            if let Some(builtin) = self.roblox_type(def_name) {
+                self.count(|stats| &mut stats.refs_resolved, 1);
+                return Ok(builtin.to_string());
+            }
+            if let Some(def_schema) = self.definitions.get(def_name).cloned() {
+                self.count(|stats| &mut stats.refs_resolved, 1);
+                if self.options.inline_all {
+                    return self.inline_definition(def_name, &def_schema);
+                }
+                return Ok(self
+                    .renderer
+                    .reference(ref_path, &self.definition_type_name(def_name)));
+            }
         }
 
-        Err(ConversionError::UnsupportedType(format!(
-            "Unsupported $ref: {}",
-            ref_path
-        )))
+        // Lenient conversion degrades to `any`, recorded by an `@ref` comment
+        if self.options.lenient_refs {
+            self.diagnostics
+                .warn(format!("unresolved $ref '{}' converted to any", ref_path));
+            if !self.unresolved_refs.iter().any(|r| r == ref_path) {
+                self.unresolved_refs.push(ref_path.to_string());
+            }
+            return Ok("any".to_string());
+        }
+
+        // Local pointers into anything but the definitions are not followed
+        if ref_path.starts_with('#') && def_name.is_none() {
+            return Err(ConversionError::InvalidSchema(format!(
+                "$ref '{}' points inside the schema; only '#', '#/$defs/...' and '#/definitions/...' are supported",
+                ref_path
+            )));
+        }
+
+        // Keep the document part so callers can resolve it against a base path
+        let path = ref_path.split('#').next().unwrap_or_default();
+        Err(ConversionError::UnresolvedRef {
+            reference: ref_path.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Get the key a definition name is stored under for the root being converted
+    fn scoped_definition_key<'a>(&'a self, def_name: &'a str) -> &'a str {
+        self.scoped_definitions
+            .get(&self.root_name)
+            .and_then(|defs| defs.get(def_name))
+            .map_or(def_name, String::as_str)
+    }
+
+    /// Add to a conversion statistic unless the schema is only being described in a comment
+    fn count(&mut self, stat: fn(&mut ConversionStats) -> &mut usize, amount: usize) {
+        if self.describing == 0 {
+            *stat(&mut self.diagnostics.stats) += amount;
+        }
+    }
+
+    /// Get the inline type of a schema with the `$ref`s lenient conversion replaced with `any` inside it
+    fn inline_type_with_refs(&mut self, schema: &JsonSchema) -> Result<(String, Vec<String>)> {
+        let outer_refs = std::mem::take(&mut self.unresolved_refs);
+        let result = self.inline_type(schema);
+        let refs = std::mem::replace(&mut self.unresolved_refs, outer_refs);
+        result.map(|inline| (inline, refs))
+    }
+
+    /// Write an `@ref` comment for each reference converted to `any`
+    fn format_unresolved_refs(&self, refs: &[String], indent_str: &str) -> String {
+        if !self.options.emit_comments {
+            return String::new();
+        }
+        refs.iter()
+            .map(|ref_path| format!("{}--- @ref {}\n", indent_str, ref_path))
+            .collect()
+    }
+
+    /// Expand a definition inline, falling back to `any` for recursive references
+    fn inline_definition(&mut self, def_name: &str, def_schema: &JsonSchema) -> Result<String> {
+        if self.inlining.iter().any(|name| name == def_name) {
+            return Ok("any".to_string());
+        }
+        self.inlining.push(def_name.to_string());
+        let result = self.inline_type(def_schema);
+        self.inlining.pop();
+        result
+    }
+
+    /// Get the declaration keyword for a type, exporting it unless writing declarations or an export list excludes it
+    fn type_keyword(&self, name: &str) -> &'static str {
+        if self.options.declarations {
+            return "type";
+        }
+        match &self.options.export_types {
+            Some(exported) if !exported.contains(name) => "type",
+            _ => "export type",
+        }
+    }
+
+    /// Build the emitted PascalCase name for a root or definition, including any prefix
+    ///
+    /// Punctuation that cannot appear in an identifier becomes `_`, and a name that is
+    /// empty or starts with a digit is prefixed with `_`.
+    fn type_name(&self, name: &str) -> String {
+        let mut sanitized: String = self
+            .unsanitized_type_name(name)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if sanitized.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+            sanitized.insert(0, '_');
+        }
+        sanitized
+    }
+
+    /// Build the emitted name of a root, preferring its title when enabled
+    fn root_type_name(&self, name: &str, schema: &JsonSchema) -> String {
+        self.type_name(self.schema_title(schema).unwrap_or(name))
+    }
+
+    /// Give every root a unique emitted name, suffixing names an earlier root already took
+    fn assign_root_names(&mut self, roots: &[(String, JsonSchema)]) {
+        let mut root_names: Vec<String> = Vec::with_capacity(roots.len());
+        for (name, schema) in roots {
+            let base = self.root_type_name(name, schema);
+            let mut type_name = base.clone();
+            let mut suffix = 2;
+            while root_names.contains(&type_name) {
+                type_name = format!("{}{}", base, suffix);
+                suffix += 1;
+            }
+            if type_name != base {
+                self.diagnostics.warn(format!(
+                    "type name '{}' for '{}' collides with another type, emitted as '{}'",
+                    base, name, type_name
+                ));
+            }
+            root_names.push(type_name);
+        }
+        self.root_names = root_names;
+    }
+
+    /// Give every definition a unique emitted name
+    ///
+    /// Root names and their named union variants are reserved first. Definitions are then
+    /// taken in key order, with root-scoped conflicting definitions last: those whose name is
+    /// still free keep it, and the rest get the first free `2`, `3`, ... suffix.
+    fn assign_type_names(&mut self, roots: &[(String, JsonSchema)]) {
+        let mut taken: HashSet<String> = HashSet::new();
+        if !self.options.definitions_only {
+            for (type_name, (_, schema)) in self.root_names.iter().zip(roots) {
+                taken.extend(self.variant_names(type_name, schema));
+                taken.insert(type_name.clone());
+            }
+        }
+
+        // Definitions scoped to a later root come after the ones they conflict with
+        let scoped: HashSet<&String> = self
+            .scoped_definitions
+            .values()
+            .flat_map(|defs| defs.values())
+            .collect();
+        let mut def_names: Vec<_> = self.definitions.keys().cloned().collect();
+        def_names.sort_by(|a, b| (scoped.contains(a), a).cmp(&(scoped.contains(b), b)));
+        let mut type_names = HashMap::with_capacity(def_names.len());
+        let mut colliding = Vec::new();
+        for def_name in def_names {
+            let base = self.type_name(&self.definition_base_name(&def_name));
+            let variants = self.variant_names(&base, &self.definitions[&def_name]);
+            if taken.contains(&base) || variants.iter().any(|v| taken.contains(v)) {
+                colliding.push((def_name, base));
+                continue;
+            }
+            taken.extend(variants);
+            taken.insert(base.clone());
+            type_names.insert(def_name, base);
+        }
+        for (def_name, base) in colliding {
+            let mut suffix = 2;
+            let (name, variants) = loop {
+                let name = format!("{}{}", base, suffix);
+                let variants = self.variant_names(&name, &self.definitions[&def_name]);
+                if !taken.contains(&name) && !variants.iter().any(|v| taken.contains(v)) {
+                    break (name, variants);
+                }
+                suffix += 1;
+            };
+            taken.extend(variants);
+            taken.insert(name.clone());
+            type_names.insert(def_name, name);
+        }
+        self.type_names = type_names;
+    }
+
+    /// List the `<Name>VariantN` types a declaration may extract from its union branches
+    fn variant_names(&self, name: &str, schema: &JsonSchema) -> Vec<String> {
+        let JsonSchema::Object(obj) = schema else {
+            return Vec::new();
+        };
+        if !self.options.named_union_variants {
+            return Vec::new();
+        }
+        let branches = obj.one_of.as_ref().or(obj.any_of.as_ref());
+        branches
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter(|(_, branch)| obj.properties.is_some() || Self::is_object_branch(branch))
+            .map(|(i, _)| format!("{}Variant{}", name, i + 1))
+            .collect()
+    }
+
+    /// Get the emitted name for a definition, as assigned by `assign_type_names`
+    fn definition_type_name(&self, def_name: &str) -> String {
+        match self.type_names.get(def_name) {
+            Some(name) => name.clone(),
+            None => self.type_name(&self.definition_base_name(def_name)),
+        }
+    }
+
+    /// Get the name a definition is emitted under before casing: its title when enabled, else its key
+    fn definition_base_name(&self, def_name: &str) -> String {
+        // Nested and root-scoped definitions are named after their own key, not their full key
+        let key = def_name.rsplit(['/', '#']).next().unwrap_or(def_name);
+        self.definitions
+            .get(def_name)
+            .and_then(|schema| self.schema_title(schema))
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    /// Get the `title` naming a root or definition, if title-based naming is enabled
+    fn schema_title<'a>(&self, schema: &'a JsonSchema) -> Option<&'a str> {
+        match schema {
+            JsonSchema::Object(obj) if self.options.title_names => obj.title.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Build the PascalCase name for a root or definition before sanitizing it
+    fn unsanitized_type_name(&self, name: &str) -> String {
+        let pascal = name.to_case(Case::Pascal);
+        match &self.options.type_prefix {
+            Some(prefix) => format!("{}{}", prefix, pascal),
+            None => pascal,
+        }
+    }
+
+    /// Warn when a root or definition name had to be sanitized into a valid identifier
+    fn warn_invalid_type_name(&mut self, name: &str) {
+        let type_name = self.type_name(name);
+        let unsanitized = self.unsanitized_type_name(name);
+        if unsanitized != type_name {
+            self.diagnostics.warn(format!(
+                "type name '{}' for '{}' is not a valid Luau identifier, emitted as '{}'",
+                unsanitized, name, type_name
+            ));
+        }
+    }
+
+    /// Warn when a definition's name collided with another type and was suffixed by `assign_type_names`
+    fn warn_colliding_type_name(&mut self, def_name: &str) {
+        let Some(type_name) = self.type_names.get(def_name) else {
+            return;
+        };
+        let base = self.type_name(&self.definition_base_name(def_name));
+        if base != *type_name {
+            self.diagnostics.warn(format!(
+                "type name '{}' for '{}' collides with another type, emitted as '{}'",
+                base, def_name, type_name
+            ));
+        }
+    }
+
+    /// Get the Roblox built-in type named by a format or definition, if enabled
+    fn roblox_type(&self, name: &str) -> Option<&'static str> {
+        const ROBLOX_TYPES: [&str; 18] = [
+            "Axes",
+            "BrickColor",
+            "CFrame",
+            "Color3",
+            "ColorSequence",
+            "Faces",
+            "Instance",
+            "NumberRange",
+            "NumberSequence",
+            "PhysicalProperties",
+            "Ray",
+            "Rect",
+            "Region3",
+            "TweenInfo",
+            "UDim",
+            "UDim2",
+            "Vector2",
+            "Vector3",
+        ];
+        if !self.options.roblox_types {
+            return None;
+        }
+        ROBLOX_TYPES
+            .into_iter()
+            .find(|builtin| builtin.eq_ignore_ascii_case(name))
+    }
+
+    /// Format a number literal, printing whole numbers without a fractional part
+    fn format_number(value: f64) -> String {
+        if value.fract() == 0.0 && value.abs() < 1e15 {
+            format!("{}", value as i64)
+        } else {
+            value.to_string()
+        }
     }
 
     /// Create indent string for given level
@@ -805,19 +2947,85 @@ impl SchemaConverter {
     }
 
     /// Method for formatting constraints with indentation
-    fn format_constraints_with_indent(&self, schema: &JsonSchema, indent_str: &str) -> String {
+    fn format_constraints_with_indent(&mut self, schema: &JsonSchema, indent_str: &str) -> String {
         let mut output = String::new();
 
+        if !self.options.emit_comments {
+            return output;
+        }
+
+        // Record the key of a wrapper object replaced by its value type
+        if let JsonSchema::Object(obj) = schema
+            && let Some((key, _)) = self.unwrapped_property(obj)
+        {
+            output.push_str(&format!(
+                "{}--- @unwrapped {}\n",
+                indent_str,
+                Self::luau_key(key)
+            ));
+        }
+
+        if !self.options.emit_constraints {
+            return output;
+        }
+
         if let JsonSchema::Object(obj) = schema {
+            let start = output.len();
+            Self::add_literal_values_indent(obj, indent_str, &mut output);
             self.add_numeric_constraints_indent(obj, indent_str, &mut output);
             self.add_string_constraints_indent(obj, indent_str, &mut output);
             self.add_array_constraints_indent(obj, indent_str, &mut output);
+            self.add_tuple_comment(obj, indent_str, &mut output);
             self.add_object_constraints_indent(obj, indent_str, &mut output);
+            let lines = output[start..].lines().count();
+            self.count(|stats| &mut stats.constraint_comments, lines);
         }
 
         output
     }
 
+    /// Add an `@enum` or `@const` comment listing values whose literal type had to be widened
+    ///
+    /// Luau only has string and boolean singleton types, so numeric values become `number`.
+    fn add_literal_values_indent(obj: &SchemaObject, indent_str: &str, output: &mut String) {
+        if let Some(value) = &obj.const_ {
+            if Self::is_widened_literal(value) {
+                output.push_str(&format!(
+                    "{}--- @const {}\n",
+                    indent_str,
+                    json_value_to_luau(value)
+                ));
+            }
+            return;
+        }
+        let Some(values) = obj.enum_.clone().or_else(|| Self::const_branch_values(obj)) else {
+            return;
+        };
+        if !values.iter().any(Self::is_widened_literal) {
+            return;
+        }
+        let mut listed: Vec<String> = Vec::with_capacity(values.len());
+        for value in &values {
+            let literal = json_value_to_luau(value);
+            if !listed.contains(&literal) {
+                listed.push(literal);
+            }
+        }
+        output.push_str(&format!("{}--- @enum {}\n", indent_str, listed.join(", ")));
+    }
+
+    /// Check whether a value has no Luau singleton type and is widened in literal types
+    ///
+    /// Arrays and objects only keep their element and field types, so their exact values are lost too.
+    fn is_widened_literal(value: &serde_json::Value) -> bool {
+        matches!(
+            value,
+            serde_json::Value::Number(_)
+                | serde_json::Value::Array(_)
+                | serde_json::Value::Object(_)
+        )
+    }
+
     /// Add numeric constraints with indentation
     fn add_numeric_constraints_indent(
         &self,
@@ -826,19 +3034,46 @@ impl SchemaConverter {
         output: &mut String,
     ) {
         if let Some(min) = obj.minimum {
-            output.push_str(&format!("{}--- @minimum {}\n", indent_str, min));
+            output.push_str(&format!(
+                "{}--- @minimum {}\n",
+                indent_str,
+                Self::format_number(min)
+            ));
         }
         if let Some(max) = obj.maximum {
-            output.push_str(&format!("{}--- @maximum {}\n", indent_str, max));
+            output.push_str(&format!(
+                "{}--- @maximum {}\n",
+                indent_str,
+                Self::format_number(max)
+            ));
         }
         if let Some(ex_min) = obj.exclusive_minimum {
-            output.push_str(&format!("{}--- @exclusiveMinimum {}\n", indent_str, ex_min));
+            output.push_str(&format!(
+                "{}--- @exclusiveMinimum {}\n",
+                indent_str,
+                Self::format_number(ex_min)
+            ));
         }
         if let Some(ex_max) = obj.exclusive_maximum {
-            output.push_str(&format!("{}--- @exclusiveMaximum {}\n", indent_str, ex_max));
+            output.push_str(&format!(
+                "{}--- @exclusiveMaximum {}\n",
+                indent_str,
+                Self::format_number(ex_max)
+            ));
+        }
+        // Unions lose the integer distinction once mapped to `number`
+        if let Some(SchemaType::Multiple(types)) = &obj.type_
+            && types.contains(&SingleType::Integer)
+            && !types.contains(&SingleType::Number)
+        {
+            output.push_str(&format!("{}--- @integer\n", indent_str));
         }
         if let Some(multiple) = obj.multiple_of {
-            output.push_str(&format!("{}--- @multipleOf {}\n", indent_str, multiple));
+            output.push_str(&format!(
+                "{}--- @multipleOf {}\n",
+                indent_str,
+                Self::format_number(multiple)
+            ));
         }
     }
 
@@ -849,17 +3084,82 @@ impl SchemaConverter {
         indent_str: &str,
         output: &mut String,
     ) {
-        if let Some(min_len) = obj.min_length {
-            output.push_str(&format!("{}--- @minLength {}\n", indent_str, min_len));
-        }
-        if let Some(max_len) = obj.max_length {
-            output.push_str(&format!("{}--- @maxLength {}\n", indent_str, max_len));
+        let mut constraints: Vec<(&str, String)> = Vec::new();
+        match (obj.min_length, obj.max_length) {
+            // Equal bounds describe a fixed-length string
+            (Some(min_len), Some(max_len)) if min_len == max_len => {
+                constraints.push(("length", min_len.to_string()));
+            }
+            (min_length, max_length) => {
+                if let Some(min_len) = min_length {
+                    constraints.push(("minLength", min_len.to_string()));
+                }
+                if let Some(max_len) = max_length {
+                    constraints.push(("maxLength", max_len.to_string()));
+                }
+            }
         }
         if let Some(pattern) = &obj.pattern {
-            output.push_str(&format!("{}--- @pattern {}\n", indent_str, pattern));
+            constraints.push(("pattern", self.format_pattern(pattern)));
+        }
+
+        if self.options.group_string_constraints && !constraints.is_empty() {
+            let grouped: Vec<_> = constraints
+                .iter()
+                .map(|(tag, value)| format!("{}={}", tag, value))
+                .collect();
+            output.push_str(&format!(
+                "{}--- @string {}\n",
+                indent_str,
+                grouped.join(" ")
+            ));
+        } else {
+            for (tag, value) in &constraints {
+                output.push_str(&format!("{}--- @{} {}\n", indent_str, tag, value));
+            }
         }
         if let Some(format) = &obj.format {
-            output.push_str(&format!("{}--- @format {}\n", indent_str, format));
+            match Self::known_format_description(format) {
+                Some(description) if self.options.describe_known_formats => {
+                    output.push_str(&format!(
+                        "{}--- @format {} ({})\n",
+                        indent_str, format, description
+                    ));
+                }
+                _ => output.push_str(&format!("{}--- @format {}\n", indent_str, format)),
+            }
+        }
+    }
+
+    /// Format a regex for a comment, wrapping it in a markdown code span when enabled
+    fn format_pattern(&self, pattern: &str) -> String {
+        if !self.options.pattern_code_span {
+            return pattern.to_string();
+        }
+        // The fence must be longer than any backtick run inside the pattern
+        let longest_run = pattern.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest_run + 1);
+        if longest_run > 0 || pattern.starts_with(' ') || pattern.ends_with(' ') {
+            format!("{} {} {}", fence, pattern, fence)
+        } else {
+            format!("{}{}{}", fence, pattern, fence)
+        }
+    }
+
+    /// Describe a well-known string format
+    fn known_format_description(format: &str) -> Option<&'static str> {
+        match format {
+            "date-time" => Some("RFC 3339 date-time"),
+            "date" => Some("RFC 3339 full-date"),
+            "time" => Some("RFC 3339 full-time"),
+            "duration" => Some("ISO 8601 duration"),
+            "email" => Some("email address"),
+            "hostname" => Some("internet hostname"),
+            "ipv4" => Some("IPv4 address"),
+            "ipv6" => Some("IPv6 address"),
+            "uri" => Some("URI"),
+            "uuid" => Some("UUID"),
+            _ => None,
         }
     }
 
@@ -870,15 +3170,29 @@ impl SchemaConverter {
         indent_str: &str,
         output: &mut String,
     ) {
-        if let Some(min_items) = obj.min_items {
-            output.push_str(&format!("{}--- @minItems {}\n", indent_str, min_items));
-        }
-        if let Some(max_items) = obj.max_items {
-            output.push_str(&format!("{}--- @maxItems {}\n", indent_str, max_items));
+        match (obj.min_items, obj.max_items) {
+            // Equal bounds describe a fixed-size array
+            (Some(min_items), Some(max_items)) if min_items == max_items => {
+                output.push_str(&format!("{}--- @size {}\n", indent_str, min_items));
+            }
+            (min_items, max_items) => {
+                if let Some(min_items) = min_items {
+                    output.push_str(&format!("{}--- @minItems {}\n", indent_str, min_items));
+                }
+                if let Some(max_items) = max_items {
+                    output.push_str(&format!("{}--- @maxItems {}\n", indent_str, max_items));
+                }
+            }
         }
         if let Some(true) = obj.unique_items {
             output.push_str(&format!("{}--- @uniqueItems true\n", indent_str));
         }
+        if obj.items.is_none() && obj.contains.is_some() && self.options.contains_as_items {
+            output.push_str(&format!(
+                "{}--- @contains element type is a lower bound\n",
+                indent_str
+            ));
+        }
     }
 
     /// Add object constraints with indentation
@@ -897,11 +3211,56 @@ impl SchemaConverter {
     }
 }
 
+/// Format a JSON value as a Luau literal
+///
+/// Strings are escaped, `null` becomes `nil`, and arrays and objects become table constructors.
+pub fn json_value_to_luau(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "nil".to_string(),
+        serde_json::Value::Bool(_) | serde_json::Value::Number(_) => value.to_string(),
+        serde_json::Value::String(s) => SchemaConverter::luau_string(s),
+        serde_json::Value::Array(items) if items.is_empty() => "{}".to_string(),
+        serde_json::Value::Array(items) => {
+            let items: Vec<_> = items.iter().map(json_value_to_luau).collect();
+            format!("{{ {} }}", items.join(", "))
+        }
+        serde_json::Value::Object(fields) if fields.is_empty() => "{}".to_string(),
+        serde_json::Value::Object(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(k, v)| {
+                    format!(
+                        "{} = {}",
+                        SchemaConverter::luau_key(k),
+                        json_value_to_luau(v)
+                    )
+                })
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+    }
+}
+
 impl Clone for SchemaConverter {
     fn clone(&self) -> Self {
         Self {
             definitions: self.definitions.clone(),
             generated_types: self.generated_types.clone(),
+            options: self.options.clone(),
+            renderer: Arc::clone(&self.renderer),
+            depth: self.depth,
+            inlining: self.inlining.clone(),
+            diagnostics: self.diagnostics.clone(),
+            root_name: self.root_name.clone(),
+            root_names: self.root_names.clone(),
+            definition_roots: self.definition_roots.clone(),
+            scoped_definitions: self.scoped_definitions.clone(),
+            roots: self.roots.clone(),
+            shared_enums: self.shared_enums.clone(),
+            type_names: self.type_names.clone(),
+            unresolved_refs: self.unresolved_refs.clone(),
+            tuple_members: self.tuple_members.clone(),
+            describing: self.describing,
         }
     }
 }
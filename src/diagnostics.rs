@@ -0,0 +1,59 @@
+/// Non-fatal findings collected while converting a schema
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics {
+    /// Problems found in the schema that did not stop the conversion
+    pub warnings: Vec<String>,
+
+    /// Keywords the output could not fully represent
+    pub unsupported_keywords: Vec<UnsupportedKeyword>,
+
+    /// Counts describing the size of the converted schema
+    pub stats: ConversionStats,
+}
+
+/// Counts of the types, references and comments produced by a conversion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConversionStats {
+    /// Object types emitted, whether declared or inline
+    pub object_types: usize,
+
+    /// Array types emitted, whether declared or inline
+    pub array_types: usize,
+
+    /// Enum unions emitted
+    pub enum_types: usize,
+
+    /// Const literal types emitted
+    pub const_types: usize,
+
+    /// Local `$ref`s resolved to a type
+    pub refs_resolved: usize,
+
+    /// Constraint comment lines such as `@minimum` emitted
+    pub constraint_comments: usize,
+}
+
+/// A schema keyword that was ignored or only approximated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedKeyword {
+    /// Keyword name, such as `not` or `patternProperties`
+    pub keyword: String,
+
+    /// JSON pointer to the schema carrying the keyword
+    pub path: String,
+}
+
+impl Diagnostics {
+    /// Record a warning
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.warnings.push(message.into());
+    }
+
+    /// Record a keyword that could not be fully represented
+    pub fn unsupported(&mut self, keyword: impl Into<String>, path: impl Into<String>) {
+        self.unsupported_keywords.push(UnsupportedKeyword {
+            keyword: keyword.into(),
+            path: path.into(),
+        });
+    }
+}
@@ -7,6 +7,12 @@ pub enum ConversionError {
     #[error("Failed to parse JSON Schema: {0}")]
     ParseError(String),
 
-    #[error("Unsupported schema type: {0}")]
-    UnsupportedType(String),
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+
+    #[error("Unresolved reference: {reference} (document: '{path}')")]
+    UnresolvedRef { reference: String, path: String },
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
 }
@@ -1,15 +1,44 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
 pub mod converter;
+pub mod diagnostics;
 pub mod error;
+pub mod options;
+pub mod renderer;
 pub mod schema;
 
-pub use converter::SchemaConverter;
+pub use converter::{SchemaConverter, json_value_to_luau};
+pub use diagnostics::{ConversionStats, Diagnostics, UnsupportedKeyword};
 pub use error::{ConversionError, Result};
-pub use schema::JsonSchema;
+pub use options::{
+    ConverterOptions, DefinitionOrder, FieldSeparator, LuauMode, NullableStyle, PropertyOrder,
+};
+pub use renderer::{DefaultRenderer, TypeRenderer};
+pub use schema::{JsonSchema, SchemaObject};
+
+/// Parse a JSON Schema string without converting it
+pub fn parse_schema(json_schema: &str) -> Result<JsonSchema> {
+    serde_json::from_str(json_schema).map_err(|e| ConversionError::ParseError(e.to_string()))
+}
+
+/// Parse a JSON Schema from a reader without buffering the whole document as a string
+pub fn parse_schema_reader<R: Read>(reader: R) -> Result<JsonSchema> {
+    serde_json::from_reader(reader).map_err(|e| ConversionError::ParseError(e.to_string()))
+}
+
+/// Convert a JSON Schema read from a reader to Luau type definitions
+pub fn convert_reader<R: Read>(reader: R) -> Result<String> {
+    let schema = parse_schema_reader(reader)?;
+
+    let converter = SchemaConverter::new();
+    converter.convert(&schema)
+}
 
 /// Convert a JSON Schema string to Luau type definitions
 pub fn convert_schema(json_schema: &str) -> Result<String> {
-    let schema: JsonSchema = serde_json::from_str(json_schema)
-        .map_err(|e| ConversionError::ParseError(e.to_string()))?;
+    let schema = parse_schema(json_schema)?;
 
     let converter = SchemaConverter::new();
     converter.convert(&schema)
@@ -17,9 +46,52 @@ pub fn convert_schema(json_schema: &str) -> Result<String> {
 
 /// Convert a JSON Schema string to Luau with a custom type name
 pub fn convert_schema_with_name(json_schema: &str, type_name: &str) -> Result<String> {
-    let schema: JsonSchema = serde_json::from_str(json_schema)
-        .map_err(|e| ConversionError::ParseError(e.to_string()))?;
+    let schema = parse_schema(json_schema)?;
 
     let converter = SchemaConverter::new();
     converter.convert_with_name(&schema, type_name)
 }
+
+/// Convert a JSON Schema string to Luau using custom converter options
+pub fn convert_schema_with_options(
+    json_schema: &str,
+    options: &ConverterOptions,
+) -> Result<String> {
+    let schema = parse_schema(json_schema)?;
+
+    let converter = SchemaConverter::with_options(options.clone());
+    converter.convert(&schema)
+}
+
+/// Convert a JSON Schema string and write the Luau output to a file
+///
+/// The output is written to a temporary file next to `path` and renamed into place,
+/// so a failed conversion or write never leaves a partial file behind.
+pub fn convert_schema_to_file(
+    json_schema: &str,
+    path: &Path,
+    type_name: Option<&str>,
+) -> Result<()> {
+    let luau = match type_name {
+        Some(name) => convert_schema_with_name(json_schema, name)?,
+        None => convert_schema(json_schema)?,
+    };
+
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid output path: {}", path.display()),
+        )
+    })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    fs::write(&temp_path, luau)?;
+    if let Err(e) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
@@ -1,16 +1,18 @@
-use clap::Parser;
-use json_schema_to_luau::{convert_schema, convert_schema_with_name};
+use clap::{Parser, ValueEnum};
+use json_schema_to_luau::{
+    ConverterOptions, JsonSchema, LuauMode, SchemaConverter, parse_schema_reader,
+};
 use std::fs;
-use std::io::{self, Read};
-use std::path::PathBuf;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "json-schema-to-luau")]
 #[command(about = "Convert JSON Schema to Luau type definitions", long_about = None)]
 struct Cli {
     /// Input JSON Schema file (use '-' for stdin)
-    #[arg(value_name = "INPUT")]
-    input: String,
+    #[arg(value_name = "INPUT", required_unless_present = "manifest")]
+    input: Option<String>,
 
     /// Output file (defaults to stdout)
     #[arg(short, long, value_name = "FILE")]
@@ -19,29 +21,181 @@ struct Cli {
     /// Custom type name for the root schema
     #[arg(short, long, value_name = "NAME")]
     type_name: Option<String>,
+
+    /// Emit a Luau type-checking mode directive as the first line
+    #[arg(long, value_enum, value_name = "MODE")]
+    mode: Option<Mode>,
+
+    /// Expand every $ref inline instead of emitting separate definitions
+    #[arg(long)]
+    inline_all: bool,
+
+    /// Emit only the $defs/definitions types, skipping the root type
+    #[arg(long)]
+    definitions_only: bool,
+
+    /// Manifest file listing `input output` pairs to convert, one per line
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["input", "output"])]
+    manifest: Option<PathBuf>,
+
+    /// Record the input file name in a `--- Source:` comment (ignored for stdin)
+    #[arg(long)]
+    source_comment: bool,
+
+    /// Collapse unions with more members than this into their common supertype
+    #[arg(long, value_name = "N")]
+    max_union_members: Option<usize>,
+
+    /// Emit a .d.luau declaration file with no runtime code
+    #[arg(long)]
+    declarations: bool,
+
+    /// Template file whose `{{types}}` placeholder is replaced by the generated types
+    #[arg(long, value_name = "FILE")]
+    template: Option<PathBuf>,
+}
+
+/// Luau type-checking mode accepted by `--mode`
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Strict,
+    Nonstrict,
+    Nocheck,
+}
+
+impl From<Mode> for LuauMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Strict => LuauMode::Strict,
+            Mode::Nonstrict => LuauMode::Nonstrict,
+            Mode::Nocheck => LuauMode::Nocheck,
+        }
+    }
+}
+
+/// Placeholder in a `--template` file replaced by the generated types
+const TEMPLATE_PLACEHOLDER: &str = "{{types}}";
+
+impl Cli {
+    /// Convert one schema document read from `source` using the CLI options
+    fn convert(
+        &self,
+        schema: &JsonSchema,
+        source: Option<&Path>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let converter = SchemaConverter::with_options(ConverterOptions {
+            mode: self.mode.map(Into::into),
+            inline_all: self.inline_all,
+            definitions_only: self.definitions_only,
+            source_comment: source
+                .filter(|_| self.source_comment)
+                .map(|path| path.display().to_string()),
+            max_union_members: self.max_union_members,
+            declarations: self.declarations,
+            ..Default::default()
+        });
+        let luau =
+            converter.convert_with_name(schema, self.type_name.as_deref().unwrap_or("Root"))?;
+
+        match &self.template {
+            Some(template) => apply_template(template, &luau),
+            None => Ok(luau),
+        }
+    }
+}
+
+/// Substitute the generated types for the placeholder in a template file
+fn apply_template(template: &Path, luau: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(template)?;
+    if !content.contains(TEMPLATE_PLACEHOLDER) {
+        return Err(format!(
+            "{}: template has no `{}` placeholder",
+            template.display(),
+            TEMPLATE_PLACEHOLDER
+        )
+        .into());
+    }
+    Ok(content.replace(TEMPLATE_PLACEHOLDER, luau.trim_end_matches('\n')))
+}
+
+/// Parse a manifest into `(input, output)` pairs relative to the manifest's directory
+///
+/// Blank lines and lines starting with `#` are ignored.
+fn parse_manifest(
+    manifest: &Path,
+    content: &str,
+) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn std::error::Error>> {
+    let base = manifest.parent().unwrap_or(Path::new(""));
+    let mut entries = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let [input, output] = parts[..] else {
+            return Err(format!(
+                "{}:{}: expected `input output`, found `{}`",
+                manifest.display(),
+                index + 1,
+                line
+            )
+            .into());
+        };
+        entries.push((base.join(input), base.join(output)));
+    }
+    Ok(entries)
+}
+
+/// Convert every manifest entry, reporting each one's status on stderr
+fn run_manifest(cli: &Cli, manifest: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = parse_manifest(manifest, &fs::read_to_string(manifest)?)?;
+    let mut failed = 0;
+    for (input, output) in &entries {
+        let result = fs::File::open(input)
+            .map_err(Into::into)
+            .and_then(|file| Ok(parse_schema_reader(BufReader::new(file))?))
+            .and_then(|schema| cli.convert(&schema, Some(input)))
+            .and_then(|luau| Ok(fs::write(output, luau)?));
+        match result {
+            Ok(()) => eprintln!("ok: {} -> {}", input.display(), output.display()),
+            Err(e) => {
+                failed += 1;
+                eprintln!("failed: {}: {}", input.display(), e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} manifest entries failed", failed, entries.len()).into());
+    }
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
-    // Read input
-    let input_content = if cli.input == "-" {
-        let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer)?;
-        buffer
+    if let Some(manifest) = &cli.manifest {
+        return run_manifest(&cli, manifest);
+    }
+
+    // Parse input straight from the reader
+    let input = cli.input.as_deref().unwrap_or("-");
+    let (schema, source) = if input == "-" {
+        (parse_schema_reader(io::stdin().lock())?, None)
     } else {
-        fs::read_to_string(&cli.input)?
+        let file = fs::File::open(input)?;
+        (
+            parse_schema_reader(BufReader::new(file))?,
+            Some(Path::new(input)),
+        )
     };
 
     // Convert schema
-    let luau_types = if let Some(type_name) = cli.type_name {
-        convert_schema_with_name(&input_content, &type_name)?
-    } else {
-        convert_schema(&input_content)?
-    };
+    let luau_types = cli.convert(&schema, source)?;
 
     // Write output
-    if let Some(output_path) = cli.output {
+    if let Some(output_path) = &cli.output {
         fs::write(output_path, luau_types)?;
     } else {
         println!("{}", luau_types);
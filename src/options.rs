@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+/// Options controlling how Luau type definitions are emitted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConverterOptions {
+    /// Move an array's element type onto its own line when it is longer than this many characters
+    pub multiline_array_threshold: Option<usize>,
+
+    /// Indentation level applied to every top-level declaration
+    pub base_indent: usize,
+
+    /// Emit the root `$schema` and `$id` as provenance comments at the top of the output
+    pub emit_provenance: bool,
+
+    /// Maximum schema nesting depth before conversion fails
+    pub max_depth: usize,
+
+    /// Emit a trailing comment counting the generated types
+    pub emit_summary: bool,
+
+    /// Emit description, constraint and composition comments
+    pub emit_comments: bool,
+
+    /// Tag properties missing from `required` with an `@optional` comment
+    pub emit_optional_tag: bool,
+
+    /// Reject `required` entries with no matching property instead of emitting them as `any`
+    pub strict: bool,
+
+    /// Extract object branches of `anyOf`/`oneOf` into named `<Name>VariantN` types
+    pub named_union_variants: bool,
+
+    /// Emit a `@default` comment summarizing the root object's property defaults
+    pub emit_default_summary: bool,
+
+    /// Prefix prepended to the root and every definition name
+    pub type_prefix: Option<String>,
+
+    /// Luau type-checking mode directive emitted as the first line
+    pub mode: Option<LuauMode>,
+
+    /// Expand every `$ref` inline instead of emitting separate definitions
+    pub inline_all: bool,
+
+    /// Type arrays without `items` by their `contains` schema
+    pub contains_as_items: bool,
+
+    /// Emit each `examples` entry as an `@example` comment
+    pub emit_examples: bool,
+
+    /// Separator written after each field of a table type
+    pub field_separator: FieldSeparator,
+
+    /// Write a separator after the last field of a multi-line table type
+    pub trailing_separator: bool,
+
+    /// Map `format` values and definition names such as `Vector3` to Roblox built-in types
+    pub roblox_types: bool,
+
+    /// Skip the root type and emit only the `$defs`/`definitions` types
+    pub definitions_only: bool,
+
+    /// Describe well-known formats such as `date-time`, `uuid` and `email` in their `@format` comment
+    pub describe_known_formats: bool,
+
+    /// Wrap union and intersection declarations longer than this many characters, one member per line
+    pub union_wrap_width: Option<usize>,
+
+    /// Emit descriptions above type declarations
+    pub emit_type_descriptions: bool,
+
+    /// Emit descriptions above object properties
+    pub emit_property_descriptions: bool,
+
+    /// Emit constraint annotations such as `@minimum` and `@format`
+    pub emit_constraints: bool,
+
+    /// How nullable type unions are written
+    pub nullable_style: NullableStyle,
+
+    /// Order in which object properties are written
+    pub property_order: PropertyOrder,
+
+    /// Order in which `$defs`/`definitions` types are written
+    pub definition_order: DefinitionOrder,
+
+    /// Text of a comment banner written before the first definition, such as `=== Definitions ===`
+    pub definitions_banner: Option<String>,
+
+    /// Convert unresolvable `$ref`s to `any` with an `@ref` comment instead of failing
+    pub lenient_refs: bool,
+
+    /// Emitted type names declared with `export type`; others use plain `type`. `None` exports every type
+    pub export_types: Option<HashSet<String>>,
+
+    /// Wrap `@pattern` regexes in a markdown code span so editors render them literally
+    pub pattern_code_span: bool,
+
+    /// Combine a string's length and pattern constraints into one `@string` comment line
+    pub group_string_constraints: bool,
+
+    /// Emit string enums shared by several properties once as a named type and reference it
+    pub hoist_shared_enums: bool,
+
+    /// Number of blank lines written between top-level declarations
+    pub blank_lines_between: usize,
+
+    /// Input the output was generated from, recorded in a `--- Source:` header comment
+    pub source_comment: Option<String>,
+
+    /// Collapse unions and enums with more members than this into their common supertype
+    pub max_union_members: Option<usize>,
+
+    /// Write `any?` for optional `any` fields; when disabled they are written `any`, which already admits `nil`
+    pub mark_optional_any: bool,
+
+    /// Maximum number of `@example` comments per schema before noting how many were left out
+    pub max_examples: Option<usize>,
+
+    /// Name the root and definitions after their `title` when one is present
+    pub title_names: bool,
+
+    /// Emit each `x-<name>` vendor extension as an `@<name>` comment
+    pub emit_extensions: bool,
+
+    /// Emit a `@jsonschema` comment above each type holding its constraints as JSON
+    pub emit_schema_metadata: bool,
+
+    /// Experimental: write objects wrapping a single required property as that property's type
+    pub unwrap_single_property: bool,
+
+    /// Emit a `.d.luau` declaration file: plain `type` declarations and no trailing `return {}`
+    pub declarations: bool,
+}
+
+impl Default for ConverterOptions {
+    fn default() -> Self {
+        Self {
+            multiline_array_threshold: None,
+            base_indent: 0,
+            emit_provenance: false,
+            max_depth: 64,
+            emit_summary: false,
+            emit_comments: true,
+            emit_optional_tag: false,
+            strict: false,
+            named_union_variants: false,
+            emit_default_summary: false,
+            type_prefix: None,
+            mode: None,
+            inline_all: false,
+            contains_as_items: false,
+            emit_examples: false,
+            field_separator: FieldSeparator::Comma,
+            trailing_separator: true,
+            roblox_types: false,
+            definitions_only: false,
+            describe_known_formats: false,
+            union_wrap_width: None,
+            emit_type_descriptions: true,
+            emit_property_descriptions: true,
+            emit_constraints: true,
+            nullable_style: NullableStyle::Question,
+            property_order: PropertyOrder::Alphabetical,
+            definition_order: DefinitionOrder::Alphabetical,
+            definitions_banner: None,
+            lenient_refs: false,
+            export_types: None,
+            pattern_code_span: false,
+            group_string_constraints: false,
+            hoist_shared_enums: false,
+            blank_lines_between: 1,
+            source_comment: None,
+            max_union_members: None,
+            mark_optional_any: true,
+            max_examples: None,
+            title_names: false,
+            emit_extensions: false,
+            emit_schema_metadata: false,
+            unwrap_single_property: false,
+            declarations: false,
+        }
+    }
+}
+
+/// Luau type-checking mode selected by a `--!` directive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuauMode {
+    Strict,
+    Nonstrict,
+    Nocheck,
+}
+
+impl LuauMode {
+    /// Get the directive line selecting this mode
+    pub fn directive(self) -> &'static str {
+        match self {
+            LuauMode::Strict => "--!strict",
+            LuauMode::Nonstrict => "--!nonstrict",
+            LuauMode::Nocheck => "--!nocheck",
+        }
+    }
+}
+
+/// Separator between the fields of a table type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSeparator {
+    Comma,
+    Semicolon,
+}
+
+impl FieldSeparator {
+    /// Get the separator character
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FieldSeparator::Comma => ",",
+            FieldSeparator::Semicolon => ";",
+        }
+    }
+}
+
+/// Spelling of a type that may also be `nil`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullableStyle {
+    /// Write `T?`
+    Question,
+    /// Write `T | nil`
+    UnionNil,
+}
+
+/// Order of the fields in a table type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyOrder {
+    /// Sort every property by name
+    Alphabetical,
+    /// List required properties before optional ones, each group sorted by name
+    RequiredFirst,
+}
+
+/// Order of the definition types after the root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionOrder {
+    /// Sort every definition by name
+    Alphabetical,
+    /// List definitions in the order they are first referenced, breadth-first from the root
+    Reference,
+}
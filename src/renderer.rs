@@ -0,0 +1,38 @@
+use crate::schema::SchemaObject;
+
+/// Hooks for customizing how schema shapes are rendered as Luau types
+///
+/// Every method has a default implementation producing the converter's standard
+/// output, so implementors only override the shapes they care about.
+pub trait TypeRenderer: Send + Sync {
+    /// Render a scalar type (`string`, `number`, `boolean` or `nil`)
+    fn scalar(&self, _schema: &SchemaObject, luau_type: &str) -> String {
+        luau_type.to_string()
+    }
+
+    /// Render an object type, either a multi-line table or an inline one
+    fn object(&self, _schema: &SchemaObject, luau_type: &str) -> String {
+        luau_type.to_string()
+    }
+
+    /// Render a union of already rendered member types
+    fn union(&self, members: &[String]) -> String {
+        members.join(" | ")
+    }
+
+    /// Render an intersection of already rendered member types
+    fn intersection(&self, members: &[String]) -> String {
+        members.join(" & ")
+    }
+
+    /// Render a reference to a named definition
+    fn reference(&self, _ref_path: &str, type_name: &str) -> String {
+        type_name.to_string()
+    }
+}
+
+/// Renderer producing the converter's standard output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRenderer;
+
+impl TypeRenderer for DefaultRenderer {}
@@ -37,7 +37,13 @@ pub struct SchemaObject {
     pub required: Option<Vec<String>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub items: Option<Box<JsonSchema>>,
+    pub items: Option<Items>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix_items: Option<Vec<JsonSchema>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_items: Option<Box<JsonSchema>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "enum")]
@@ -143,6 +149,100 @@ pub struct SchemaObject {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "else")]
     pub else_: Option<Box<JsonSchema>>,
+
+    // Keywords not modeled above, such as `x-` vendor extensions
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
+}
+
+impl SchemaObject {
+    /// Create schema with a single type
+    pub fn of_type(type_: SingleType) -> Self {
+        Self {
+            type_: Some(SchemaType::Single(type_)),
+            ..Default::default()
+        }
+    }
+
+    /// Create object schema
+    pub fn object() -> Self {
+        Self::of_type(SingleType::Object)
+    }
+
+    /// Create array schema with the given item schema
+    pub fn array(items: impl Into<JsonSchema>) -> Self {
+        Self {
+            items: Some(Items::Single(Box::new(items.into()))),
+            ..Self::of_type(SingleType::Array)
+        }
+    }
+
+    /// Create string schema
+    pub fn string() -> Self {
+        Self::of_type(SingleType::String)
+    }
+
+    /// Create number schema
+    pub fn number() -> Self {
+        Self::of_type(SingleType::Number)
+    }
+
+    /// Create integer schema
+    pub fn integer() -> Self {
+        Self::of_type(SingleType::Integer)
+    }
+
+    /// Create boolean schema
+    pub fn boolean() -> Self {
+        Self::of_type(SingleType::Boolean)
+    }
+
+    /// Add a property schema
+    pub fn property(mut self, name: impl Into<String>, schema: impl Into<JsonSchema>) -> Self {
+        self.properties
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), schema.into());
+        self
+    }
+
+    /// Mark properties as required
+    pub fn required<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required
+            .get_or_insert_with(Default::default)
+            .extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl From<SchemaObject> for JsonSchema {
+    fn from(obj: SchemaObject) -> Self {
+        JsonSchema::Object(Box::new(obj))
+    }
+}
+
+impl From<bool> for JsonSchema {
+    fn from(value: bool) -> Self {
+        JsonSchema::Boolean(value)
+    }
+}
+
+/// Array `items`: one schema for every element, or the legacy positional tuple form
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Items {
+    Single(Box<JsonSchema>),
+    Tuple(Vec<JsonSchema>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1,4 +1,12 @@
-use json_schema_to_luau::{convert_schema, convert_schema_with_name};
+// The CLI tests pass argument arrays by reference
+#![allow(clippy::needless_borrows_for_generic_args)]
+
+use json_schema_to_luau::{
+    ConversionError, ConversionStats, ConverterOptions, DefinitionOrder, FieldSeparator,
+    JsonSchema, LuauMode, NullableStyle, PropertyOrder, SchemaConverter, SchemaObject,
+    TypeRenderer, UnsupportedKeyword, convert_reader, convert_schema, convert_schema_to_file,
+    convert_schema_with_name, convert_schema_with_options, json_value_to_luau, parse_schema,
+};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
@@ -197,3 +205,3468 @@ fn test_library_malformed_json() {
     let result = convert_schema(malformed_json);
     assert!(result.is_err(), "Should return error for malformed JSON");
 }
+
+/// Test rendering scalars, arrays and nested objects as Luau literals
+#[test]
+fn test_library_json_value_to_luau() {
+    use serde_json::json;
+
+    assert_eq!(json_value_to_luau(&json!(null)), "nil");
+    assert_eq!(json_value_to_luau(&json!(true)), "true");
+    assert_eq!(json_value_to_luau(&json!(false)), "false");
+    assert_eq!(json_value_to_luau(&json!(42)), "42");
+    assert_eq!(json_value_to_luau(&json!(-1.5)), "-1.5");
+    assert_eq!(
+        json_value_to_luau(&json!("say \"hi\"\n")),
+        r#""say \"hi\"\n""#
+    );
+
+    assert_eq!(json_value_to_luau(&json!([])), "{}");
+    assert_eq!(
+        json_value_to_luau(&json!([1, "a", null])),
+        r#"{ 1, "a", nil }"#
+    );
+    assert_eq!(json_value_to_luau(&json!({})), "{}");
+    assert_eq!(
+        json_value_to_luau(&json!({
+            "name": "box",
+            "size": { "w": 2, "h": [1, 2] },
+            "end": true
+        })),
+        r#"{ ["end"] = true, name = "box", size = { h = { 1, 2 }, w = 2 } }"#
+    );
+}
+
+/// Test that long array element types are moved onto their own line
+#[test]
+fn test_multiline_array_element_type() {
+    let schema: JsonSchema = serde_json::from_str(
+        r#"{
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "a": { "type": "string" },
+                    "b": { "type": "number" }
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        multiline_array_threshold: Some(10),
+        ..Default::default()
+    });
+    let result = converter.convert(&schema).unwrap();
+
+    assert!(
+        result.contains("export type Root = {\n    { a: string?, b: number? }\n}"),
+        "Array element type not emitted on its own line: {}",
+        result
+    );
+
+    // Short element types stay on one line
+    let result = convert_schema(r#"{ "type": "array", "items": { "type": "string" } }"#).unwrap();
+    assert!(result.contains("export type Root = { string }"));
+}
+
+/// Test that enum and const definitions are emitted and referenced by name
+#[test]
+fn test_ref_to_enum_and_const_definitions() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "color": { "$ref": "#/$defs/Color" },
+            "kind": { "$ref": "#/$defs/Kind" }
+        },
+        "required": ["color"],
+        "$defs": {
+            "Color": { "type": "string", "enum": ["red", "green", "blue"] },
+            "Kind": { "const": "fixed" }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    color: Color,"));
+    assert!(result.contains("    kind: Kind?,"));
+    assert!(result.contains("export type Color = \"red\" | \"green\" | \"blue\""));
+    assert!(result.contains("export type Kind = \"fixed\""));
+}
+
+/// Test that an empty property schema becomes an optional `any` field
+#[test]
+fn test_empty_property_schema() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "x": {},
+            "y": { "description": "Anything goes" }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    x: any?,"));
+    assert!(result.contains("    --- Anything goes\n    y: any?,"));
+}
+
+/// Test that every top-level declaration is indented by the base level
+#[test]
+fn test_base_indent() {
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "description": "A user",
+            "properties": {
+                "tag": { "$ref": "#/$defs/Tag" }
+            },
+            "$defs": {
+                "Tag": { "type": "string", "maxLength": 8 }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        base_indent: 1,
+        ..Default::default()
+    });
+    let result = converter.convert(&schema).unwrap();
+
+    assert!(result.contains("    --- A user\n    export type Root = {\n        tag: Tag?,\n    }"));
+    assert!(result.contains("    --- @maxLength 8\n    export type Tag = string"));
+    assert!(!result.contains("\nexport type"));
+}
+
+/// Test building a schema in Rust and converting it
+#[test]
+fn test_schema_builder() {
+    let schema: JsonSchema = SchemaObject::object()
+        .description("A user")
+        .property("name", SchemaObject::string())
+        .property("age", SchemaObject::integer())
+        .property("tags", SchemaObject::array(SchemaObject::string()))
+        .required(["name"])
+        .into();
+
+    let result = SchemaConverter::new()
+        .convert_with_name(&schema, "User")
+        .unwrap();
+
+    assert!(result.contains("--- A user\nexport type User = {"));
+    assert!(result.contains("    age: number?,"));
+    assert!(result.contains("    name: string,"));
+    assert!(result.contains("    tags: { string }?,"));
+}
+
+/// Test that a string enum keeps its @format comment
+#[test]
+fn test_enum_with_format() {
+    let schema = r#"{
+        "type": "string",
+        "format": "date",
+        "enum": ["2024-01-01", "2024-12-31"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("--- @format date\nexport type Root = \"2024-01-01\" | \"2024-12-31\"")
+    );
+
+    // Enum properties keep the comment as well
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "day": { "type": "string", "format": "date", "enum": ["2024-01-01"] }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    --- @format date\n    day: \"2024-01-01\"?,"));
+}
+
+/// Test emitting `$schema` and `$id` provenance comments
+#[test]
+fn test_provenance_comments() {
+    let schema: JsonSchema = serde_json::from_str(
+        r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": "https://example.com/user.schema.json",
+            "type": "string"
+        }"#,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        emit_provenance: true,
+        ..Default::default()
+    });
+    let result = converter.convert(&schema).unwrap();
+
+    assert!(result.starts_with(
+        "--- @schema https://json-schema.org/draft/2020-12/schema\n\
+         --- @id https://example.com/user.schema.json\n\
+         \n\
+         export type Root = string"
+    ));
+
+    // Disabled by default
+    let result = SchemaConverter::new().convert(&schema).unwrap();
+    assert!(!result.contains("@schema"));
+
+    // Skipped along with every other comment
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        emit_provenance: true,
+        emit_comments: false,
+        ..Default::default()
+    });
+    let result = converter.convert(&schema).unwrap();
+    assert!(result.starts_with("export type Root = string"));
+}
+
+/// Test that a nullable enum with a type array keeps every literal as an optional union
+#[test]
+fn test_enum_with_type_array() {
+    let schema = r#"{ "type": ["string", "null"], "enum": ["a", "b", null] }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("export type Root = (\"a\" | \"b\")?"));
+}
+
+/// Test overriding scalar rendering with a custom renderer
+#[test]
+fn test_custom_type_renderer() {
+    struct StrRenderer;
+
+    impl TypeRenderer for StrRenderer {
+        fn scalar(&self, _schema: &SchemaObject, luau_type: &str) -> String {
+            match luau_type {
+                "string" => "str".to_string(),
+                other => other.to_string(),
+            }
+        }
+    }
+
+    let schema: JsonSchema = serde_json::from_str(
+        r#"{
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "number" },
+                "id": { "type": ["string", "number"] }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::new().with_renderer(StrRenderer);
+    let result = converter.convert(&schema).unwrap();
+
+    assert!(result.contains("    name: str?,"));
+    assert!(result.contains("    age: number?,"));
+    assert!(result.contains("    id: (str | number)?,"));
+}
+
+/// Test that the object, union, intersection and reference hooks are called
+#[test]
+fn test_custom_renderer_hooks() {
+    struct TaggingRenderer;
+
+    impl TypeRenderer for TaggingRenderer {
+        fn object(&self, _schema: &SchemaObject, luau_type: &str) -> String {
+            format!("Obj<{}>", luau_type)
+        }
+
+        fn union(&self, members: &[String]) -> String {
+            members.join(" OR ")
+        }
+
+        fn intersection(&self, members: &[String]) -> String {
+            members.join(" AND ")
+        }
+
+        fn reference(&self, ref_path: &str, type_name: &str) -> String {
+            format!("Ref<{}@{}>", type_name, ref_path)
+        }
+    }
+
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "point": { "type": "object", "properties": { "x": { "type": "number" } } },
+                "id": { "anyOf": [{ "type": "string" }, { "type": "boolean" }] },
+                "both": { "allOf": [{ "$ref": "#/$defs/A" }, { "$ref": "#/$defs/B" }] }
+            },
+            "$defs": {
+                "A": { "type": "string" },
+                "B": { "type": "number" }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::new().with_renderer(TaggingRenderer);
+    let result = converter.convert(&schema).unwrap();
+
+    assert!(
+        result.contains("export type Root = Obj<{\n"),
+        "Object hook not called: {}",
+        result
+    );
+    assert!(result.contains("    point: Obj<{ x: number? }>?,"));
+    assert!(result.contains("    id: (string OR boolean)?,"));
+    assert!(result.contains("    both: (Ref<A@#/$defs/A> AND Ref<B@#/$defs/B>)?,"));
+}
+
+/// Test that schemas nested beyond the depth limit fail gracefully
+#[test]
+fn test_max_depth_exceeded() {
+    let mut schema = SchemaObject::string();
+    for _ in 0..20 {
+        schema = SchemaObject::object().property("child", schema);
+    }
+    let schema: JsonSchema = schema.into();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        max_depth: 10,
+        ..Default::default()
+    });
+    let result = converter.convert(&schema);
+
+    assert!(
+        matches!(result, Err(ConversionError::InvalidSchema(ref msg)) if msg == "max depth exceeded"),
+        "Expected max depth error, got {:?}",
+        result
+    );
+
+    // The default limit handles the same schema
+    assert!(SchemaConverter::new().convert(&schema).is_ok());
+
+    // Keywords only visited by the pre-conversion walks are limited too
+    let mut nested = r#"{ "type": "string" }"#.to_string();
+    for _ in 0..20 {
+        nested = format!(r#"{{ "type": "array", "contains": {} }}"#, nested);
+    }
+    let schema = format!(
+        r##"{{ "type": "object", "properties": {{ "deep": {} }}, "$defs": {{ "Deep": {} }} }}"##,
+        nested, nested
+    );
+    for options in [
+        ConverterOptions {
+            max_depth: 10,
+            ..Default::default()
+        },
+        ConverterOptions {
+            max_depth: 10,
+            definition_order: DefinitionOrder::Reference,
+            hoist_shared_enums: true,
+            ..Default::default()
+        },
+    ] {
+        let result = convert_schema_with_options(&schema, &options);
+        assert!(
+            matches!(result, Err(ConversionError::InvalidSchema(ref msg)) if msg == "max depth exceeded"),
+            "Expected max depth error from the walks, got {:?}",
+            result
+        );
+    }
+}
+
+/// Test that literal patternProperties become fields and wildcard patterns an index
+#[test]
+fn test_pattern_properties_literal_and_wildcard() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" }
+        },
+        "patternProperties": {
+            "^id$": { "type": "number" },
+            "^x-": { "type": "string" }
+        },
+        "required": ["id"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root = {\n    id: number,\n    name: string?,\n    --- @patternProperty ^x-\n    [string]: string,\n}"
+        ),
+        "Unexpected pattern properties output: {}",
+        result
+    );
+}
+
+/// Test that patternProperties comments are sorted by pattern
+#[test]
+fn test_pattern_properties_comment_order() {
+    let schema = r#"{
+        "type": "object",
+        "patternProperties": {
+            "^z-": { "type": "number" },
+            "^a-": { "type": "string" },
+            "^m-": { "type": "boolean" }
+        }
+    }"#;
+
+    let expected = "    --- @patternProperty ^a-\n    --- @patternProperty ^m-\n    --- @patternProperty ^z-\n    [string]: string | boolean | number,\n";
+    for _ in 0..5 {
+        let result = convert_schema(schema).unwrap();
+        assert!(
+            result.contains(expected),
+            "Pattern comments not sorted: {}",
+            result
+        );
+    }
+}
+
+/// Test objects defined only by patternProperties producing a unioned string index
+#[test]
+fn test_pattern_properties_only_object() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "labels": {
+                "type": "object",
+                "patternProperties": {
+                    "^a-": { "type": "string" },
+                    "^b-": { "type": "number" }
+                }
+            }
+        },
+        "patternProperties": {
+            "^x-": { "type": "boolean" }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("    labels: { [string]: string | number }?,"),
+        "Inline pattern index missing: {}",
+        result
+    );
+    assert!(result.contains("    --- @patternProperty ^x-\n    [string]: boolean,"));
+    assert!(!result.contains("[string]: any"));
+}
+
+/// Test a `^x-` pattern property as a commented index and in the inline object form
+#[test]
+fn test_pattern_properties_string_index() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "meta": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"],
+                "patternProperties": { "^x-": { "type": "string" } }
+            }
+        },
+        "required": ["meta"],
+        "patternProperties": { "^x-": { "type": "string" } }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    --- @patternProperty ^x-\n    [string]: string,\n}"),
+        "Pattern property index missing: {}",
+        result
+    );
+    assert!(result.contains("    meta: { id: string, [string]: string },"));
+}
+
+/// Test that the summary comment counts every generated type
+#[test]
+fn test_summary_comment() {
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "user": { "$ref": "#/$defs/User" },
+                "role": { "$ref": "#/$defs/Role" }
+            },
+            "$defs": {
+                "User": { "type": "object", "properties": { "name": { "type": "string" } } },
+                "Role": { "enum": ["admin", "member"] },
+                "Alias": { "$ref": "#/$defs/User" }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        emit_summary: true,
+        ..Default::default()
+    });
+    let result = converter.convert(&schema).unwrap();
+
+    let declarations = result.matches("export type ").count();
+    assert_eq!(declarations, 4);
+    assert!(result.contains(&format!(
+        "--- Generated {} types from schema\n\nreturn {{}}",
+        declarations
+    )));
+
+    // Skipped along with every other comment
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        emit_summary: true,
+        emit_comments: false,
+        ..Default::default()
+    });
+    let result = converter.convert(&schema).unwrap();
+    assert!(!result.contains("--- Generated"));
+}
+
+/// Test that properties shared alongside oneOf are merged into every branch
+#[test]
+fn test_one_of_with_shared_base() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" }
+        },
+        "required": ["id"],
+        "oneOf": [
+            { "properties": { "email": { "type": "string" } }, "required": ["email"] },
+            { "properties": { "phone": { "type": "string" } }, "required": ["phone"] }
+        ]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root = { email: string, id: string } | { id: string, phone: string }"
+        ),
+        "Shared base not merged into branches: {}",
+        result
+    );
+}
+
+/// Test the library function convert_schema_with_options with comments disabled
+#[test]
+fn test_library_convert_schema_with_options() {
+    let schema = r#"{
+        "type": "object",
+        "description": "A user",
+        "properties": {
+            "age": { "type": "number", "minimum": 0, "description": "Age in years" },
+            "id": { "anyOf": [{ "type": "string" }, { "type": "number" }] }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        emit_comments: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(!result.contains("---"), "Unexpected comment in: {}", result);
+    assert!(
+        result.contains("export type Root = {\n    age: number?,\n    id: (string | number)?,\n}")
+    );
+
+    // Default options keep comments
+    let result = convert_schema_with_options(schema, &ConverterOptions::default()).unwrap();
+    assert!(result.contains("--- A user"));
+    assert!(result.contains("    --- @minimum 0"));
+}
+
+/// Test the @optional tag on properties that are not required
+#[test]
+fn test_optional_tag() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "nickname": { "type": ["string", "null"] }
+        },
+        "required": ["name"]
+    }"#;
+
+    let options = ConverterOptions {
+        emit_optional_tag: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(result.contains("    name: string,"));
+    assert!(!result.contains("--- @optional\n    name"));
+    assert!(result.contains("    --- @optional\n    nickname: string?,"));
+}
+
+/// Test required keys that have no matching property
+#[test]
+fn test_required_without_property() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" }
+        },
+        "required": ["name", "ghost"]
+    }"#;
+
+    // Lenient mode emits the key as any
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    ghost: any,\n    name: string,"));
+
+    // Strict mode rejects the schema
+    let options = ConverterOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options);
+    assert!(
+        matches!(result, Err(ConversionError::InvalidSchema(ref msg)) if msg.contains("ghost")),
+        "Expected invalid schema error, got {:?}",
+        result
+    );
+}
+
+/// Test that enum and const on array items produce literal element types
+#[test]
+fn test_array_items_enum_and_const() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "fixed": { "type": "array", "items": { "const": "fixed" } },
+            "levels": { "type": "array", "items": { "enum": [1, 2, 3] } },
+            "tags": { "type": "array", "items": { "enum": ["a", "b"] } }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    fixed: { \"fixed\" }?,"));
+    assert!(result.contains("    levels: { number }?,"));
+    assert!(result.contains("    tags: { \"a\" | \"b\" }?,"));
+
+    let result = convert_schema(r#"{ "type": "array", "items": { "enum": ["a", "b"] } }"#).unwrap();
+    assert!(result.contains("export type Root = { \"a\" | \"b\" }"));
+}
+
+/// Test extracting object branches of a union into named variant types
+#[test]
+fn test_named_union_variants() {
+    let schema = r#"{
+        "anyOf": [
+            { "type": "object", "properties": { "email": { "type": "string" } }, "required": ["email"] },
+            { "type": "object", "properties": { "phone": { "type": "string" } }, "required": ["phone"] },
+            { "type": "string" }
+        ]
+    }"#;
+
+    let options = ConverterOptions {
+        named_union_variants: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains("export type Root = RootVariant1 | RootVariant2 | string"),
+        "Union not built from named variants: {}",
+        result
+    );
+    assert!(result.contains("export type RootVariant1 = {\n    email: string,\n}"));
+    assert!(result.contains("export type RootVariant2 = {\n    phone: string,\n}"));
+
+    // Disabled by default
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("export type Root = { email: string } | { phone: string } | string"));
+}
+
+/// Test the aggregated @default comment for root property defaults
+#[test]
+fn test_default_summary_comment() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "default": "guest" },
+            "retries": { "type": "number", "default": 3 },
+            "tags": { "type": "array", "items": { "type": "string" }, "default": ["a"] },
+            "id": { "type": "string" }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        emit_default_summary: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.starts_with(
+            "--- @default { name = \"guest\", retries = 3, tags = { \"a\" } }\nexport type Root = {"
+        ),
+        "Unexpected default summary: {}",
+        result
+    );
+
+    // Disabled by default
+    let result = convert_schema(schema).unwrap();
+    assert!(!result.contains("@default"));
+
+    // Skipped along with every other comment
+    let options = ConverterOptions {
+        emit_default_summary: true,
+        emit_comments: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.starts_with("export type Root = {"));
+}
+
+/// Test that a $ref in additionalProperties resolves to the named type
+#[test]
+fn test_additional_properties_ref() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "meta": { "type": "object", "additionalProperties": { "$ref": "#/$defs/Value" } }
+        },
+        "additionalProperties": { "$ref": "#/$defs/Value" },
+        "$defs": {
+            "Value": { "type": "string", "minLength": 1 }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root = {\n    meta: { [string]: Value }?,\n    [string]: Value,\n}"
+        ),
+        "Ref not resolved in index signature: {}",
+        result
+    );
+    assert!(result.contains("--- @minLength 1\nexport type Value = string"));
+}
+
+/// Test that the type prefix applies to every emitted name and reference
+#[test]
+fn test_type_prefix() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "user": { "$ref": "#/$defs/User" }
+        },
+        "$defs": {
+            "User": { "type": "object", "properties": { "name": { "type": "string" } } }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        type_prefix: Some("Api".to_string()),
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(result.contains("export type ApiRoot = {\n    user: ApiUser?,\n}"));
+    assert!(result.contains("export type ApiUser = {\n    name: string?,\n}"));
+    assert!(!result.contains("type Root"));
+    assert!(!result.contains("type User"));
+}
+
+/// Test the CLI with the --mode flag
+#[test]
+fn test_cli_with_mode() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "json-schema-to-luau",
+            "--",
+            TEST_SCHEMA_PATH,
+            "--mode",
+            "strict",
+        ])
+        .output()
+        .expect("Failed to execute CLI command with mode");
+
+    assert!(
+        output.status.success(),
+        "CLI command with mode failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let cli_output = String::from_utf8(output.stdout).expect("CLI output is not valid UTF-8");
+
+    assert!(
+        cli_output.starts_with("--!strict\n"),
+        "Mode directive not on the first line: {}",
+        cli_output
+    );
+    assert_eq!(cli_output.matches("--!").count(), 1);
+}
+
+/// Test CLI with --source-comment recording the input file
+#[test]
+fn test_cli_with_source_comment() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "json-schema-to-luau",
+            "--",
+            TEST_SCHEMA_PATH,
+            "--source-comment",
+            "--mode",
+            "strict",
+        ])
+        .output()
+        .expect("Failed to execute CLI command with source comment");
+
+    assert!(
+        output.status.success(),
+        "CLI command with source comment failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let cli_output = String::from_utf8(output.stdout).expect("CLI output is not valid UTF-8");
+    assert!(
+        cli_output.starts_with(&format!(
+            "--!strict\n\n--- Source: {}\n\n",
+            TEST_SCHEMA_PATH
+        )),
+        "Source comment missing: {}",
+        cli_output
+    );
+}
+
+/// Test CLI with --declarations emitting a declaration file without runtime code
+#[test]
+fn test_cli_with_declarations() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "json-schema-to-luau",
+            "--",
+            TEST_SCHEMA_PATH,
+            "--declarations",
+        ])
+        .output()
+        .expect("Failed to execute CLI command with declarations");
+
+    assert!(
+        output.status.success(),
+        "CLI command with declarations failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let cli_output = String::from_utf8(output.stdout).expect("CLI output is not valid UTF-8");
+    assert!(cli_output.contains("type Root = {"));
+    assert!(!cli_output.contains("export type"));
+    assert!(
+        !cli_output.contains("return"),
+        "Runtime code in declarations: {}",
+        cli_output
+    );
+}
+
+/// Test CLI with --template substituting the generated types for the placeholder
+#[test]
+fn test_cli_with_template() {
+    let dir = std::env::temp_dir().join("json_schema_to_luau_template_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let template = dir.join("module.luau.in");
+    fs::write(
+        &template,
+        "-- Generated module\n{{types}}\n-- End of module\n",
+    )
+    .unwrap();
+    fs::write(dir.join("name.json"), r#"{ "type": "string" }"#).unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "json-schema-to-luau", "--"])
+        .arg(dir.join("name.json"))
+        .arg("--template")
+        .arg(&template)
+        .output()
+        .expect("Failed to execute CLI command with template");
+
+    assert!(
+        output.status.success(),
+        "CLI command with template failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let cli_output = String::from_utf8(output.stdout).expect("CLI output is not valid UTF-8");
+    assert_eq!(
+        cli_output,
+        "-- Generated module\nexport type Root = string\n\nreturn {}\n-- End of module\n\n"
+    );
+
+    // A template without the placeholder is rejected
+    fs::write(&template, "-- No placeholder\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "json-schema-to-luau", "--"])
+        .arg(dir.join("name.json"))
+        .arg("--template")
+        .arg(&template)
+        .output()
+        .expect("Failed to execute CLI command with template");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("{{types}}"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test CLI converting every entry of a manifest file
+#[test]
+fn test_cli_with_manifest() {
+    let dir = std::env::temp_dir().join("json_schema_to_luau_manifest_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy(TEST_SCHEMA_PATH, dir.join("user.json")).unwrap();
+    fs::write(dir.join("name.json"), r#"{ "type": "string" }"#).unwrap();
+    let manifest = dir.join("schemas.txt");
+    fs::write(
+        &manifest,
+        "user.json user.luau\nname.json out/../name.luau\n",
+    )
+    .unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "json-schema-to-luau", "--", "--manifest"])
+        .arg(&manifest)
+        .output()
+        .expect("Failed to execute CLI command with manifest");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success(),
+        "Missing output directory did not fail: {}",
+        stderr
+    );
+    assert!(stderr.contains("ok: "), "{}", stderr);
+    assert!(stderr.contains("failed: "), "{}", stderr);
+    assert!(
+        stderr.contains("1 of 2 manifest entries failed"),
+        "{}",
+        stderr
+    );
+
+    fs::write(
+        &manifest,
+        "# schemas\nuser.json user.luau\n\nname.json name.luau\n",
+    )
+    .unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "json-schema-to-luau", "--", "--manifest"])
+        .arg(&manifest)
+        .output()
+        .expect("Failed to execute CLI command with manifest");
+
+    assert!(
+        output.status.success(),
+        "CLI command with manifest failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let expected_output =
+        fs::read_to_string(EXPECTED_OUTPUT_PATH).expect("Failed to read expected output file");
+    assert_eq!(
+        fs::read_to_string(dir.join("user.luau")).unwrap().trim(),
+        expected_output.trim()
+    );
+    assert!(
+        fs::read_to_string(dir.join("name.luau"))
+            .unwrap()
+            .starts_with("export type Root = string\n")
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+/// Test that the mode directive precedes provenance comments
+#[test]
+fn test_mode_directive_order() {
+    let schema = r#"{
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "string"
+    }"#;
+
+    let options = ConverterOptions {
+        mode: Some(LuauMode::Nonstrict),
+        emit_provenance: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(result.starts_with(
+        "--!nonstrict\n\n--- @schema https://json-schema.org/draft/2020-12/schema\n\nexport type Root = string"
+    ));
+
+    // The source comment follows the directive, and only the directive stays without comments
+    let options = ConverterOptions {
+        mode: Some(LuauMode::Nonstrict),
+        source_comment: Some("user.json".to_string()),
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.starts_with("--!nonstrict\n\n--- Source: user.json\n\nexport type Root"));
+
+    let options = ConverterOptions {
+        emit_comments: false,
+        ..options
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.starts_with("--!nonstrict\n\nexport type Root = string"));
+}
+
+/// Test that equal length bounds collapse into a single @length comment
+#[test]
+fn test_fixed_length_string() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "code": { "type": "string", "minLength": 4, "maxLength": 4 },
+            "name": { "type": "string", "minLength": 1, "maxLength": 50 }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    --- @length 4\n    code: string?,"));
+    assert!(result.contains("    --- @minLength 1\n    --- @maxLength 50\n    name: string?,"));
+}
+
+/// Test that equal minItems and maxItems collapse into a single @size comment
+#[test]
+fn test_fixed_size_array() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "rgb": { "type": "array", "items": { "type": "number" }, "minItems": 3, "maxItems": 3 },
+            "tags": { "type": "array", "items": { "type": "string" }, "minItems": 1, "maxItems": 5 }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    --- @size 3\n    rgb: { number }?,"));
+    assert!(result.contains("    --- @minItems 1\n    --- @maxItems 5\n    tags: { string }?,"));
+}
+
+/// Test expanding every $ref inline with recursive references falling back to any
+#[test]
+fn test_inline_all() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "user": { "$ref": "#/$defs/User" },
+            "node": { "$ref": "#/$defs/Node" }
+        },
+        "required": ["user"],
+        "$defs": {
+            "User": {
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"]
+            },
+            "Node": {
+                "type": "object",
+                "properties": { "next": { "$ref": "#/$defs/Node" } }
+            }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        inline_all: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root = {\n    node: { next: any? }?,\n    user: { name: string },\n}"
+        ),
+        "Definitions not inlined: {}",
+        result
+    );
+    assert!(!result.contains("export type User"));
+    assert!(!result.contains("export type Node"));
+    assert_eq!(result.matches("export type ").count(), 1);
+}
+
+/// Test that duplicate enum values collapse into a single member
+#[test]
+fn test_enum_duplicate_values() {
+    let result = convert_schema(r#"{ "enum": ["a", "a", "b", "a"] }"#).unwrap();
+    assert!(result.contains("export type Root = \"a\" | \"b\"\n"));
+
+    let result = convert_schema(r#"{ "enum": ["b", null, "a", "b", null] }"#).unwrap();
+    assert!(result.contains("export type Root = (\"b\" | \"a\")?\n"));
+}
+
+/// Test null-inclusive string enums collapsing to `T?`
+#[test]
+fn test_nullable_enum_collapse() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "status": { "enum": ["active", "inactive", null] },
+            "mode": { "enum": ["auto", null] }
+        },
+        "required": ["status"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    mode: \"auto\"?,\n    status: (\"active\" | \"inactive\")?,"),
+        "Nullable enum not collapsed: {}",
+        result
+    );
+
+    let options = ConverterOptions {
+        nullable_style: NullableStyle::UnionNil,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.contains("    status: \"active\" | \"inactive\" | nil,"));
+}
+
+/// Test enums mixing scalar kinds keeping string and boolean literals and widening numbers
+#[test]
+fn test_mixed_enum_literals() {
+    let result = convert_schema(r#"{ "enum": ["auto", 0, 1] }"#).unwrap();
+    assert!(
+        result.starts_with("--- @enum \"auto\", 0, 1\nexport type Root = \"auto\" | number\n"),
+        "Mixed enum not widened: {}",
+        result
+    );
+
+    let result = convert_schema(r#"{ "enum": [true, "yes", 1.5, false] }"#).unwrap();
+    assert!(result.contains("export type Root = true | \"yes\" | number | false\n"));
+
+    let result = convert_schema(r#"{ "enum": ["off", false, null, 0] }"#).unwrap();
+    assert!(result.contains("export type Root = (\"off\" | false | number)?\n"));
+
+    let options = ConverterOptions {
+        nullable_style: NullableStyle::UnionNil,
+        ..Default::default()
+    };
+    let result =
+        convert_schema_with_options(r#"{ "enum": ["off", false, null, 0] }"#, &options).unwrap();
+    assert!(result.contains("export type Root = \"off\" | false | nil | number\n"));
+
+    // Without numbers every value stays a literal and needs no comment
+    let result = convert_schema(r#"{ "enum": ["on", true, null] }"#).unwrap();
+    assert!(result.starts_with("export type Root = (\"on\" | true)?\n"));
+}
+
+/// Test boolean const and enum fields producing singleton boolean types
+#[test]
+fn test_boolean_literal_fields() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "active": { "const": true },
+            "flag": { "enum": [true, false] },
+            "legacy": { "const": false }
+        },
+        "required": ["active", "flag"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    active: true,\n    flag: true | false,\n    legacy: false?,"),
+        "Boolean literals not kept: {}",
+        result
+    );
+}
+
+/// Test numeric enums widening to `number` with their values in an `@enum` comment
+#[test]
+fn test_numeric_enum_comment() {
+    let result = convert_schema(r#"{ "enum": [1, 2, 3] }"#).unwrap();
+    assert!(
+        result.starts_with("--- @enum 1, 2, 3\nexport type Root = number\n"),
+        "Integer enum values not documented: {}",
+        result
+    );
+
+    let result = convert_schema(r#"{ "enum": [0.5, 1.25, 0.5] }"#).unwrap();
+    assert!(result.starts_with("--- @enum 0.5, 1.25\nexport type Root = number\n"));
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "offset": { "enum": [-1, 0, -2.5] },
+            "level": { "oneOf": [{ "const": 1 }, { "const": 2 }] }
+        },
+        "required": ["level"]
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    --- @enum 1, 2\n    level: number,"));
+    assert!(result.contains("    --- @enum -1, 0, -2.5\n    offset: number?,"));
+
+    // String enums already spell out their values
+    let result = convert_schema(r#"{ "enum": ["a", "b"] }"#).unwrap();
+    assert!(!result.contains("@enum"));
+}
+
+/// Test that a `oneOf` of bare consts becomes a plain literal union
+#[test]
+fn test_const_only_one_of() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "kind": { "oneOf": [{ "const": "a" }, { "const": "b" }] }
+        },
+        "required": ["kind"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    kind: \"a\" | \"b\","),
+        "Const union not collapsed: {}",
+        result
+    );
+
+    let result = convert_schema(r#"{ "oneOf": [{ "const": "on" }, { "const": "off" }] }"#).unwrap();
+    assert!(result.starts_with("export type Root = \"on\" | \"off\"\n"));
+    assert!(!result.contains("Union type"));
+}
+
+/// Test tuple arrays declared with prefixItems
+#[test]
+fn test_prefix_items_tuples() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "point": {
+                "type": "array",
+                "prefixItems": [{ "type": "number" }, { "type": "number" }]
+            },
+            "entry": {
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "type": "integer" }, { "type": "boolean" }],
+                "items": false
+            }
+        },
+        "required": ["point", "entry"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains(
+            "    --- @tuple [string, number, boolean]\n    entry: { string | number | boolean },"
+        ),
+        "Three-element tuple not converted: {}",
+        result
+    );
+    assert!(result.contains("    --- @tuple [number, number]\n    point: { number },"));
+
+    // Elements after the tuple positions join the element union
+    let result = convert_schema(
+        r#"{ "type": "array", "prefixItems": [{ "type": "string" }, { "type": "number" }], "items": { "type": "boolean" } }"#,
+    )
+    .unwrap();
+    assert!(result.starts_with(
+        "--- @tuple [string, number]\nexport type Root = { string | number | boolean }\n"
+    ));
+
+    // Positions are converted once, so a lenient ref is recorded on its own field only
+    let options = ConverterOptions {
+        lenient_refs: true,
+        ..Default::default()
+    };
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "a": { "type": "array", "prefixItems": [{ "$ref": "common.json#/$defs/Id" }, { "type": "string" }] },
+            "b": { "type": "string" }
+        }
+    }"##;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains(
+            "    --- @ref common.json#/$defs/Id\n    --- @tuple [any, string]\n    a: { any | string }?,\n    b: string?,"
+        ),
+        "Tuple position converted twice: {}",
+        result
+    );
+    assert_eq!(result.matches("@ref").count(), 1);
+}
+
+/// Test the legacy array form of `items` producing a tuple like `prefixItems`
+#[test]
+fn test_items_array_tuple() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "pair": {
+                "type": "array",
+                "items": [{ "type": "string" }, { "type": "integer" }]
+            }
+        },
+        "required": ["pair"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    --- @tuple [string, number]\n    pair: { string | number },"),
+        "Array-form items not converted: {}",
+        result
+    );
+
+    // `additionalItems` types the elements after the tuple positions
+    let result = convert_schema(
+        r#"{ "type": "array", "items": [{ "type": "string" }], "additionalItems": { "type": "boolean" } }"#,
+    )
+    .unwrap();
+    assert!(result.starts_with("--- @tuple [string]\nexport type Root = { string | boolean }\n"));
+}
+
+/// Test that a nullable array property uses its items type and collapses null
+#[test]
+fn test_nullable_array_property() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "aliases": { "type": ["array", "null"], "items": { "type": "string" } },
+            "ids": { "type": ["array", "number"], "items": { "type": "number" } }
+        },
+        "required": ["ids"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("    aliases: { string }?,"),
+        "Nullable array not collapsed: {}",
+        result
+    );
+    assert!(result.contains("    ids: ({ number } | number),"));
+}
+
+/// Test that a pure $ref definition is emitted as a one-line alias
+#[test]
+fn test_alias_definition() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "owner": { "$ref": "#/$defs/Owner" }
+        },
+        "$defs": {
+            "User": { "type": "object", "properties": { "name": { "type": "string" } } },
+            "Owner": { "$ref": "#/$defs/User" }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    owner: Owner?,"));
+    assert!(
+        result.contains("\n\nexport type Owner = User\n\n"),
+        "Alias not emitted on one line: {}",
+        result
+    );
+    assert_eq!(result.matches("name: string?").count(), 1);
+}
+
+/// Test typing arrays without items by their contains schema
+#[test]
+fn test_contains_as_items() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "roles": { "type": "array", "contains": { "const": "admin" } }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        contains_as_items: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains(
+            "    --- @contains element type is a lower bound\n    roles: { \"admin\" }?,"
+        ),
+        "Array not typed by contains: {}",
+        result
+    );
+
+    // Disabled by default
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    roles: { any }?,"));
+}
+
+/// Test the structured error returned for external references
+#[test]
+fn test_unresolved_external_ref() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "user": { "$ref": "common.json#/$defs/User" }
+        }
+    }"##;
+
+    let result = convert_schema(schema);
+
+    assert!(
+        matches!(
+            result,
+            Err(ConversionError::UnresolvedRef { ref reference, ref path })
+                if reference == "common.json#/$defs/User" && path == "common.json"
+        ),
+        "Expected unresolved ref error, got {:?}",
+        result
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Unresolved reference: common.json#/$defs/User (document: 'common.json')"
+    );
+
+    // Local pointers outside the definitions are rejected rather than reported as external
+    let result = convert_schema(
+        r##"{ "type": "object", "properties": { "a": { "type": "string" }, "b": { "$ref": "#/properties/a" } } }"##,
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ConversionError::InvalidSchema(ref msg)) if msg.starts_with("$ref '#/properties/a' points inside the schema")
+        ),
+        "Expected local pointer error, got {:?}",
+        result
+    );
+}
+
+/// Test lenient conversion of unresolvable references to any with an @ref comment
+#[test]
+fn test_lenient_unresolved_ref() {
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "user": { "$ref": "common.json#/$defs/User" }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        lenient_refs: true,
+        ..Default::default()
+    });
+    let (result, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    assert!(
+        result.contains("    --- @ref common.json#/$defs/User\n    user: any?,"),
+        "Unresolved ref not recorded: {}",
+        result
+    );
+    assert_eq!(
+        diagnostics.warnings,
+        vec!["unresolved $ref 'common.json#/$defs/User' converted to any".to_string()]
+    );
+
+    // Root, array item and union references are recorded where they are used
+    let schema: JsonSchema =
+        serde_json::from_str(r##"{ "description": "Remote", "$ref": "common.json#/$defs/User" }"##)
+            .unwrap();
+    let result = converter.convert(&schema).unwrap();
+    assert!(
+        result
+            .starts_with("--- @ref common.json#/$defs/User\n--- Remote\nexport type Root = any\n"),
+        "Root ref not recorded: {}",
+        result
+    );
+
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "users": { "type": "array", "items": { "$ref": "common.json#/$defs/User" } },
+                "owner": { "anyOf": [{ "type": "string" }, { "$ref": "#/properties/users" }] }
+            }
+        }"##,
+    )
+    .unwrap();
+    let result = converter.convert(&schema).unwrap();
+    assert!(
+        result.contains("    --- @ref #/properties/users\n    owner: "),
+        "Union ref not recorded: {}",
+        result
+    );
+    assert!(
+        result.contains("    --- @ref common.json#/$defs/User\n    users: { any }?,"),
+        "Item ref not recorded: {}",
+        result
+    );
+
+    let items_schema: JsonSchema = serde_json::from_str(
+        r##"{ "type": "array", "items": { "$ref": "common.json#/$defs/User" } }"##,
+    )
+    .unwrap();
+    let result = converter.convert(&items_schema).unwrap();
+    assert!(result.starts_with("--- @ref common.json#/$defs/User\nexport type Root = { any }\n"));
+
+    // Only the field using the reference is commented
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "a": { "type": "string" },
+                "b": { "$ref": "common.json#/$defs/User" }
+            }
+        }"##,
+    )
+    .unwrap();
+    let result = converter.convert(&schema).unwrap();
+    assert!(
+        result.contains("    a: string?,\n    --- @ref common.json#/$defs/User\n    b: any?,"),
+        "Ref comment on the wrong field: {}",
+        result
+    );
+    assert_eq!(result.matches("@ref").count(), 1);
+
+    // A union branch ref stays on its declaration, not on a field of a later named variant
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        lenient_refs: true,
+        named_union_variants: true,
+        ..Default::default()
+    });
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "anyOf": [
+                { "$ref": "common.json#/$defs/User" },
+                { "type": "object", "properties": { "a": { "type": "string" } } }
+            ]
+        }"##,
+    )
+    .unwrap();
+    let result = converter.convert(&schema).unwrap();
+    assert!(
+        result.contains("    a: string?,"),
+        "Variant field not emitted: {}",
+        result
+    );
+    assert!(!result.contains("--- @ref common.json#/$defs/User\n    a:"));
+    assert_eq!(result.matches("@ref").count(), 1);
+
+    // The comment does not depend on constraint comments being enabled
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        lenient_refs: true,
+        emit_constraints: false,
+        ..Default::default()
+    });
+    let result = converter.convert(&items_schema).unwrap();
+    assert!(result.starts_with("--- @ref common.json#/$defs/User\nexport type Root = { any }\n"));
+}
+
+/// Test that examples are emitted and mismatched ones produce warnings
+#[test]
+fn test_examples_with_mismatch_warning() {
+    let schema: JsonSchema = serde_json::from_str(
+        r#"{
+            "type": "object",
+            "properties": {
+                "age": { "type": "integer", "examples": [42, "forty"] },
+                "role": { "enum": ["admin", "member"], "examples": ["admin"] }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        emit_examples: true,
+        ..Default::default()
+    });
+    let (result, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    assert!(result.contains("    --- @example 42\n    --- @example \"forty\"\n    age: number?,"));
+    assert!(result.contains("    --- @example \"admin\"\n    role:"));
+    assert_eq!(
+        diagnostics.warnings,
+        vec!["example \"forty\" for 'age' does not match its schema".to_string()]
+    );
+}
+
+/// Test converting a schema straight to a file
+#[test]
+fn test_library_convert_schema_to_file() {
+    let output_path = std::env::temp_dir().join("json_schema_to_luau_to_file_test.luau");
+    let _ = fs::remove_file(&output_path);
+
+    convert_schema_to_file(
+        r#"{ "type": "object", "properties": { "name": { "type": "string" } } }"#,
+        &output_path,
+        Some("User"),
+    )
+    .expect("Failed to convert schema to file");
+
+    let content = fs::read_to_string(&output_path).expect("Failed to read output file");
+    assert!(content.contains("export type User = {\n    name: string?,\n}"));
+
+    // A failed conversion leaves the existing file untouched
+    let result = convert_schema_to_file(r#"{ "type": "invalid_type" }"#, &output_path, None);
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), content);
+
+    let _ = fs::remove_file(&output_path);
+}
+
+/// Test $ref to the document root and to the definitions map itself
+#[test]
+fn test_root_and_definitions_map_refs() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "children": { "type": "array", "items": { "$ref": "#" } }
+        }
+    }"##;
+
+    let result = convert_schema_with_name(schema, "Tree").unwrap();
+    assert!(result.contains("export type Tree = {\n    children: { Tree }?,\n}"));
+
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "all": { "$ref": "#/definitions" }
+        },
+        "definitions": {
+            "User": { "type": "string" }
+        }
+    }"##;
+
+    let result = convert_schema(schema);
+    assert!(
+        matches!(result, Err(ConversionError::InvalidSchema(ref msg)) if msg.contains("definitions map")),
+        "Expected invalid schema error, got {:?}",
+        result
+    );
+}
+
+/// Test unicode property keys and enum values
+#[test]
+fn test_unicode_keys_and_enum_values() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "名前": { "type": "string" },
+            "mood": { "enum": ["café", "🎉"] },
+            "nested": { "type": "object", "properties": { "été": { "type": "number" } } }
+        },
+        "required": ["名前"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("    [\"名前\"]: string,"),
+        "Unexpected output: {}",
+        result
+    );
+    assert!(result.contains("    mood: \"café\" | \"🎉\"?,"));
+    assert!(result.contains("    nested: { [\"été\"]: number? }?,"));
+
+    // Luau identifiers are ASCII-only, so non-ASCII letters in type names are replaced
+    let result = convert_schema_with_name(r#"{ "type": "string" }"#, "größe").unwrap();
+    assert!(result.contains("export type Gr__e = string"));
+}
+
+/// Test semicolon field separators without a trailing separator
+#[test]
+fn test_field_separator_style() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "point": { "type": "object", "properties": { "x": { "type": "number" }, "y": { "type": "number" } } }
+        },
+        "required": ["name"]
+    }"#;
+
+    let options = ConverterOptions {
+        field_separator: FieldSeparator::Semicolon,
+        trailing_separator: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root = {\n    name: string;\n    point: { x: number?; y: number? }?\n}"
+        ),
+        "Unexpected separators: {}",
+        result
+    );
+
+    // The separator is left off the index signature when it is the last field
+    let schema = r#"{
+        "type": "object",
+        "properties": { "name": { "type": "string", "description": "Display name;" } },
+        "additionalProperties": { "type": "number" }
+    }"#;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.contains(
+        "export type Root = {\n    --- Display name;\n    name: string?;\n    [string]: number\n}"
+    ));
+}
+
+/// Test that a single-element type array behaves like the plain type
+#[test]
+fn test_single_element_type_array() {
+    let result = convert_schema(r#"{ "type": ["string"] }"#).unwrap();
+    assert!(result.contains("export type Root = string\n"));
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": ["string"] }
+        },
+        "required": ["name"]
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    name: string,"),
+        "Unexpected output: {}",
+        result
+    );
+}
+
+/// Test that whole-number multipleOf values print without a fraction
+#[test]
+fn test_multiple_of_integer_formatting() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "even": { "type": "integer", "multipleOf": 2 },
+            "half": { "type": "number", "multipleOf": 0.5 },
+            "step": { "type": "number", "multipleOf": 5.0 }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    --- @multipleOf 2\n    even: number?,"));
+    assert!(result.contains("    --- @multipleOf 0.5\n    half: number?,"));
+    assert!(result.contains("    --- @multipleOf 5\n    step: number?,"));
+}
+
+/// Test mapping formats and definition names to Roblox built-in types
+#[test]
+fn test_roblox_builtin_types() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "position": { "type": "object", "format": "vector3" },
+            "rotation": { "$ref": "#/$defs/CFrame" }
+        },
+        "$defs": {
+            "CFrame": { "type": "object", "properties": { "x": { "type": "number" } } }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        roblox_types: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains(
+            "    --- @format vector3\n    position: Vector3?,\n    rotation: CFrame?,\n}"
+        ),
+        "Built-in types not mapped: {}",
+        result
+    );
+    assert!(!result.contains("export type CFrame"));
+
+    // Disabled by default
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("export type CFrame = {"));
+}
+
+/// Test emitting only the definitions without the root type
+#[test]
+fn test_definitions_only() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "user": { "$ref": "#/$defs/User" }
+        },
+        "$defs": {
+            "Role": { "enum": ["admin", "member"] },
+            "User": {
+                "type": "object",
+                "properties": { "role": { "$ref": "#/$defs/Role" } }
+            }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        definitions_only: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(!result.contains("export type Root"));
+    assert!(
+        result.starts_with(
+            "export type Role = \"admin\" | \"member\"\n\nexport type User = {\n    role: Role?,\n}"
+        ),
+        "Unexpected definitions output: {}",
+        result
+    );
+
+    // References to the undeclared root fall back to any with a warning
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "$defs": {
+                "Node": { "type": "object", "properties": { "root": { "$ref": "#" } } }
+            }
+        }"##,
+    )
+    .unwrap();
+    let (result, diagnostics) = SchemaConverter::with_options(options)
+        .convert_with_diagnostics(&schema, "Root")
+        .unwrap();
+    assert!(result.starts_with("export type Node = {\n    root: any?,\n}"));
+    assert!(!result.contains("Root"));
+    assert_eq!(
+        diagnostics.warnings,
+        vec!["$ref '#' converted to any because the root type is not emitted".to_string()]
+    );
+}
+
+/// Test enums whose members are arrays
+#[test]
+fn test_enum_array_values() {
+    let result = convert_schema(r#"{ "enum": [["a", "b"], ["c"]] }"#).unwrap();
+    assert!(
+        result.starts_with(
+            "--- @enum { \"a\", \"b\" }, { \"c\" }\nexport type Root = { \"a\" | \"b\" } | { \"c\" }\n"
+        ),
+        "Unexpected array enum: {}",
+        result
+    );
+
+    // Arrays with the same element types merge into one member, but both values stay listed
+    let result = convert_schema(r#"{ "enum": [[1, 2], [3, 4]] }"#).unwrap();
+    assert!(
+        result.starts_with("--- @enum { 1, 2 }, { 3, 4 }\nexport type Root = { number }\n"),
+        "Array enum values not documented: {}",
+        result
+    );
+}
+
+/// Test const arrays mixing numbers, strings and booleans
+#[test]
+fn test_const_mixed_array() {
+    let result = convert_schema(r#"{ "const": [1, "a", true, 2, "say \"hi\""] }"#).unwrap();
+    assert!(
+        result.contains("export type Root = { number | \"a\" | true | \"say \\\"hi\\\"\" }\n"),
+        "Unexpected mixed const array: {}",
+        result
+    );
+}
+
+/// Test reusing a converter after `reset` without leaking earlier schemas
+#[test]
+fn test_converter_reset() {
+    let user: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "tag": { "$ref": "#/$defs/Tag" } },
+            "$defs": { "Tag": { "type": "string" } }
+        }"##,
+    )
+    .unwrap();
+    let order: JsonSchema = serde_json::from_str(
+        r#"{ "type": "object", "properties": { "id": { "type": "number" } } }"#,
+    )
+    .unwrap();
+
+    let mut converter = SchemaConverter::new();
+    let result = converter.convert_with_name(&user, "User").unwrap();
+    assert!(result.contains("export type Tag = string"));
+
+    converter.add_schema("User", &user);
+    converter.reset();
+    converter.add_schema("Order", &order);
+    let result = converter.finish().unwrap();
+
+    assert!(result.contains("export type Order = {\n    id: number?,\n}"));
+    assert!(
+        !result.contains("User") && !result.contains("Tag"),
+        "State leaked across reset: {}",
+        result
+    );
+}
+
+/// Test numeric consts widening to `number` with their value in a `@const` comment
+#[test]
+fn test_numeric_const_comment() {
+    let result = convert_schema(r#"{ "const": 42 }"#).unwrap();
+    assert!(
+        result.starts_with("--- @const 42\nexport type Root = number\n"),
+        "Numeric const value not documented: {}",
+        result
+    );
+
+    let result = convert_schema(r#"{ "const": 3.14 }"#).unwrap();
+    assert!(result.starts_with("--- @const 3.14\nexport type Root = number\n"));
+
+    let result = convert_schema(r#"{ "const": 0 }"#).unwrap();
+    assert!(result.starts_with("--- @const 0\nexport type Root = number\n"));
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "version": { "const": 2 },
+            "origin": { "const": [0, 0] },
+            "kind": { "const": "user" }
+        },
+        "required": ["version", "kind"]
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    kind: \"user\",\n    --- @const { 0, 0 }\n    origin: { number }?,")
+    );
+    assert!(result.contains("    --- @const 2\n    version: number,"));
+}
+
+/// Test emitting several root schemas with shared definitions
+#[test]
+fn test_add_schema_and_finish() {
+    let user: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "address": { "$ref": "#/$defs/Address" } },
+            "$defs": {
+                "Address": { "type": "object", "properties": { "city": { "type": "string" } } }
+            }
+        }"##,
+    )
+    .unwrap();
+    let company: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "office": { "$ref": "#/$defs/Address" } },
+            "$defs": {
+                "Address": { "type": "object", "properties": { "city": { "type": "string" } } }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let mut converter = SchemaConverter::new();
+    converter
+        .add_schema("user", &user)
+        .add_schema("company", &company);
+    let result = converter.finish().unwrap();
+
+    assert!(result.contains("export type User = {\n    address: Address?,\n}"));
+    assert!(result.contains("export type Company = {\n    office: Address?,\n}"));
+    assert_eq!(result.matches("export type Address = ").count(), 1);
+}
+
+/// Test scoping `$ref: "#"` to the owning root and reporting conflicting definitions across roots
+#[test]
+fn test_finish_root_scoped_definitions() {
+    let user: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "friend": { "$ref": "#/$defs/Friend" } },
+            "$defs": {
+                "Friend": { "type": "object", "properties": { "user": { "$ref": "#" } } },
+                "Id": { "type": "string" }
+            }
+        }"##,
+    )
+    .unwrap();
+    let company: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "id": { "$ref": "#/$defs/Id" } },
+            "$defs": {
+                "Id": { "type": "number" }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let mut converter = SchemaConverter::new();
+    converter
+        .add_schema("user", &user)
+        .add_schema("company", &company);
+    let (result, diagnostics) = converter.finish_with_diagnostics().unwrap();
+
+    assert!(
+        result.contains("export type Friend = {\n    user: User?,\n}"),
+        "Root ref resolved against the wrong root: {}",
+        result
+    );
+    assert!(
+        result.contains("export type Company = {\n    id: Id2?,\n}"),
+        "Conflicting definition not kept apart: {}",
+        result
+    );
+    assert!(result.contains("export type Id = string\n"));
+    assert!(result.contains("export type Id2 = number\n"));
+    assert_eq!(
+        diagnostics.warnings,
+        vec![
+            "type name 'Id' for 'Company#Id' collides with another type, emitted as 'Id2'"
+                .to_string()
+        ]
+    );
+
+    // Roots whose names collide once PascalCased are suffixed like definitions
+    let schema: JsonSchema = serde_json::from_str(r#"{ "type": "string" }"#).unwrap();
+    let mut converter = SchemaConverter::new();
+    converter.add_schema("A", &schema).add_schema("a", &schema);
+    let (result, diagnostics) = converter.finish_with_diagnostics().unwrap();
+    assert!(
+        result.contains("export type A = string\n\nexport type A2 = string\n"),
+        "Duplicate root declaration: {}",
+        result
+    );
+    assert_eq!(
+        diagnostics.warnings,
+        vec!["type name 'A' for 'a' collides with another type, emitted as 'A2'".to_string()]
+    );
+}
+
+/// Test describing well-known formats while unknown ones keep the plain comment
+#[test]
+fn test_known_format_descriptions() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "createdAt": { "type": "string", "format": "date-time" },
+            "id": { "type": "string", "format": "uuid" },
+            "sku": { "type": "string", "format": "sku-code" }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        describe_known_formats: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains("    --- @format date-time (RFC 3339 date-time)\n    createdAt: string?,")
+    );
+    assert!(result.contains("    --- @format uuid (UUID)\n    id: string?,"));
+    assert!(result.contains("    --- @format sku-code\n    sku: string?,"));
+}
+
+/// Test narrowing a type union with a type-only `not`
+#[test]
+fn test_not_narrows_type_union() {
+    let result =
+        convert_schema(r#"{ "type": ["string", "number"], "not": { "type": "number" } }"#).unwrap();
+    assert!(
+        result.contains("export type Root = string\n"),
+        "Type not narrowed: {}",
+        result
+    );
+
+    // Complex `not` schemas fall back to a comment
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "code": { "type": "string", "not": { "enum": ["none"] } }
+        }
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    --- @not \"none\"\n    code: string?,"));
+}
+
+/// Test a standalone `not` becoming a commented `any` and a `not` alongside `type`
+#[test]
+fn test_not_standalone_and_typed() {
+    let result = convert_schema(r#"{ "not": { "type": "string" } }"#).unwrap();
+    assert!(
+        result.starts_with("--- @not string\nexport type Root = any\n"),
+        "Standalone not not documented: {}",
+        result
+    );
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "label": { "type": ["string", "null"], "not": { "type": "null" } },
+            "value": { "not": { "type": "null" } },
+            "size": {
+                "type": "number",
+                "not": { "type": "object", "properties": { "x": { "type": "string" } } }
+            }
+        },
+        "required": ["label", "value"]
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    label: string,\n"));
+    assert!(result.contains("    --- @not { x: string? }\n    size: number?,"));
+    assert!(result.contains("    --- @not nil\n    value: any,"));
+}
+
+/// Test a `not` referencing a definition naming the resolved type
+#[test]
+fn test_not_ref_comment() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "not": { "$ref": "#/$defs/Banned" } }
+        },
+        "$defs": {
+            "Banned": { "enum": ["root", "admin"] }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    --- @not Banned\n    name: string?,"),
+        "Reference not resolved: {}",
+        result
+    );
+    assert!(!result.contains("$ref"));
+}
+
+/// Test wrapping wide unions with one member per line
+#[test]
+fn test_union_wrapping() {
+    let schema = r#"{
+        "anyOf": [
+            { "type": "string" },
+            { "type": "number" },
+            { "type": "boolean" },
+            { "type": "object", "properties": { "id": { "type": "string" } } }
+        ]
+    }"#;
+
+    let options = ConverterOptions {
+        union_wrap_width: Some(40),
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root =\n    | string\n    | number\n    | boolean\n    | { id: string? }\n"
+        ),
+        "Union not wrapped: {}",
+        result
+    );
+
+    // Narrow unions stay on one line
+    let result =
+        convert_schema_with_options(r#"{ "type": ["string", "number"] }"#, &options).unwrap();
+    assert!(result.contains("export type Root = string | number\n"));
+}
+
+/// Test that integer and number collapse and integer-only unions get a hint
+#[test]
+fn test_integer_number_union() {
+    let result = convert_schema(r#"{ "type": ["integer", "number"] }"#).unwrap();
+    assert!(
+        result.contains("export type Root = number\n"),
+        "Union not collapsed: {}",
+        result
+    );
+    assert!(!result.contains("@integer"));
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "count": { "type": ["integer", "null"] },
+            "total": { "type": ["integer", "number", "string"] }
+        }
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    --- @integer\n    count: number?,"));
+    assert!(result.contains("    total: (number | string)?,"));
+}
+
+/// Test parsing a schema once and converting it several times
+#[test]
+fn test_library_parse_schema() {
+    let schema =
+        parse_schema(r#"{ "type": "object", "properties": { "id": { "type": "string" } } }"#)
+            .expect("Failed to parse schema");
+
+    let converter = SchemaConverter::new();
+    let first = converter.convert(&schema).unwrap();
+    let second = converter.convert_with_name(&schema, "Item").unwrap();
+
+    assert!(first.contains("export type Root = {\n    id: string?,\n}"));
+    assert!(second.contains("export type Item = {\n    id: string?,\n}"));
+
+    assert!(matches!(
+        parse_schema(r#"{ "type": }"#),
+        Err(ConversionError::ParseError(_))
+    ));
+}
+
+/// Test converting a schema read from a reader
+#[test]
+fn test_library_convert_reader() {
+    let reader = std::io::Cursor::new(fs::read(TEST_SCHEMA_PATH).unwrap());
+    let result = convert_reader(reader).expect("Failed to convert schema from reader");
+
+    let expected_output =
+        fs::read_to_string(EXPECTED_OUTPUT_PATH).expect("Failed to read expected output file");
+    assert_eq!(result.trim(), expected_output.trim());
+
+    let reader = std::io::Cursor::new(r#"{ "type": }"#);
+    assert!(matches!(
+        convert_reader(reader),
+        Err(ConversionError::ParseError(_))
+    ));
+}
+
+/// Test toggling type descriptions, property descriptions and constraints independently
+#[test]
+fn test_granular_comment_options() {
+    let schema = r#"{
+        "type": "object",
+        "description": "A user",
+        "properties": {
+            "age": { "type": "number", "minimum": 0, "description": "Age in years" }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        emit_type_descriptions: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(!result.contains("--- A user"));
+    assert!(result.contains("    --- Age in years\n    --- @minimum 0\n    age: number?,"));
+
+    let options = ConverterOptions {
+        emit_property_descriptions: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("--- A user\nexport type Root = {\n    --- @minimum 0\n    age: number?,")
+    );
+    assert!(!result.contains("Age in years"));
+
+    let options = ConverterOptions {
+        emit_constraints: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result
+            .contains("--- A user\nexport type Root = {\n    --- Age in years\n    age: number?,")
+    );
+    assert!(!result.contains("@minimum"));
+}
+
+/// Test boolean definitions referenced from the root
+#[test]
+fn test_boolean_definitions() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "anything": { "$ref": "#/$defs/Anything" },
+            "nothing": { "$ref": "#/$defs/Nothing" }
+        },
+        "$defs": {
+            "Anything": true,
+            "Nothing": false
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    anything: Anything?,\n    nothing: Nothing?,"));
+    assert!(
+        result.contains("\n\nexport type Anything = any\n\nexport type Nothing = never\n"),
+        "Boolean definitions not emitted: {}",
+        result
+    );
+
+    // Boolean root schemas are declared too
+    let result = convert_schema("true").unwrap();
+    assert!(result.starts_with("export type Root = any\n"));
+}
+
+/// Test writing nullability as `T?` or `T | nil` on fields, optional fields and declarations
+#[test]
+fn test_nullable_style() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": ["string", "null"] },
+            "value": { "type": ["string", "boolean", "null"] },
+            "width": { "type": ["number", "null"] },
+            "xMode": { "enum": ["x", null] },
+            "zNote": { "type": "string" }
+        },
+        "required": ["name", "value"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    name: string?,\n    value: (string | boolean)?,"));
+
+    let options = ConverterOptions {
+        nullable_style: NullableStyle::UnionNil,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("    name: (string | nil),\n    value: (string | boolean | nil),"),
+        "Nullable unions not kept: {}",
+        result
+    );
+
+    // Optional fields gain `| nil` instead of `?`, without doubling an existing nil
+    assert!(
+        result.contains(
+            "    width: (number | nil),\n    xMode: \"x\" | nil,\n    zNote: string | nil,"
+        ),
+        "Optional fields not written as unions with nil: {}",
+        result
+    );
+    assert!(!result.contains('?'));
+
+    // Top-level declarations follow the same style
+    let root = r#"{ "type": ["string", "null"] }"#;
+    let result = convert_schema(root).unwrap();
+    assert!(result.starts_with("export type Root = string?\n"));
+    let result = convert_schema_with_options(root, &options).unwrap();
+    assert!(result.starts_with("export type Root = string | nil\n"));
+}
+
+/// Test inline unions with a null branch collapsing to `T?` without double-marking optional fields
+#[test]
+fn test_nullable_inline_union() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "nickname": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            "label": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            "value": { "oneOf": [{ "type": "string" }, { "type": "number" }, { "type": "null" }] }
+        },
+        "required": ["label"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains(
+            "    label: string?,\n    nickname: string?,\n    value: (string | number)?,"
+        ),
+        "Nullable union not collapsed: {}",
+        result
+    );
+
+    let options = ConverterOptions {
+        nullable_style: NullableStyle::UnionNil,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.contains("    label: (string | nil),\n    nickname: (string | nil),"));
+}
+
+/// Test resolving references to $defs declared inside a property subschema
+#[test]
+fn test_property_nested_defs() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "config": {
+                "type": "object",
+                "properties": {
+                    "entries": { "type": "array", "items": { "$ref": "#/$defs/Entry" } }
+                },
+                "$defs": {
+                    "Entry": {
+                        "type": "object",
+                        "properties": { "key": { "type": "string" } },
+                        "required": ["key"]
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(result.contains("    config: { entries: { Entry }? }?,"));
+    assert!(
+        result.contains("export type Entry = {\n    key: string,\n}"),
+        "Nested definition not emitted: {}",
+        result
+    );
+}
+
+/// Test resolving a full pointer to $defs nested inside another definition
+#[test]
+fn test_nested_definition_pointer() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "inner": { "$ref": "#/$defs/Outer/$defs/Inner" },
+            "outer": { "$ref": "#/$defs/Outer" }
+        },
+        "$defs": {
+            "Outer": {
+                "type": "object",
+                "properties": { "inner": { "$ref": "#/$defs/Outer/$defs/Inner" } },
+                "$defs": {
+                    "Inner": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } },
+                        "required": ["id"]
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("    inner: Inner?,\n    outer: Outer?,"),
+        "Nested pointer not resolved: {}",
+        result
+    );
+    assert!(result.contains("export type Inner = {\n    id: string,\n}"));
+    assert!(result.contains("export type Outer = {\n    inner: Inner?,\n}"));
+
+    // A nested definition never resolves to a top-level one sharing its name
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "nested": { "$ref": "#/$defs/Outer/$defs/Inner" },
+            "top": { "$ref": "#/$defs/Inner" }
+        },
+        "$defs": {
+            "Inner": { "type": "string" },
+            "Outer": {
+                "type": "object",
+                "$defs": { "Inner": { "type": "number" } }
+            }
+        }
+    }"##;
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    nested: Inner2?,\n    top: Inner?,"),
+        "Nested pointer resolved to the top-level definition: {}",
+        result
+    );
+    assert!(result.contains("export type Inner = string\n"));
+    assert!(result.contains("export type Inner2 = number\n"));
+
+    // Pointers below a definition that do not go through its definitions are not followed
+    let schema = r##"{
+        "type": "object",
+        "properties": { "x": { "$ref": "#/$defs/Outer/properties/x" } },
+        "$defs": {
+            "Outer": { "type": "object", "properties": { "x": { "type": "string" } } }
+        }
+    }"##;
+    match convert_schema(schema) {
+        Err(ConversionError::UnresolvedRef { reference, .. }) => {
+            assert_eq!(reference, "#/$defs/Outer/properties/x")
+        }
+        other => panic!("Expected UnresolvedRef, got {:?}", other),
+    }
+}
+
+/// Test listing required properties before optional ones
+#[test]
+fn test_property_order_required_first() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "alpha": { "type": "string" },
+            "beta": { "type": "number" },
+            "gamma": { "type": "boolean" },
+            "delta": { "type": "string" }
+        },
+        "required": ["gamma", "beta"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains(
+        "    alpha: string?,\n    beta: number,\n    delta: string?,\n    gamma: boolean,"
+    ));
+
+    let options = ConverterOptions {
+        property_order: PropertyOrder::RequiredFirst,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains(
+            "    beta: number,\n    gamma: boolean,\n    alpha: string?,\n    delta: string?,"
+        ),
+        "Required properties not listed first: {}",
+        result
+    );
+}
+
+/// Test listing definitions in the order they are first referenced from the root
+#[test]
+fn test_definition_reference_order() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "a": { "$ref": "#/$defs/Zebra" },
+            "b": { "$ref": "#/$defs/Apple" }
+        },
+        "$defs": {
+            "Apple": { "type": "string" },
+            "Mango": { "type": "number" },
+            "Zebra": {
+                "type": "object",
+                "properties": { "next": { "$ref": "#/$defs/Kiwi" } }
+            },
+            "Kiwi": { "type": "boolean" }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(result.find("type Apple").unwrap() < result.find("type Zebra").unwrap());
+
+    let options = ConverterOptions {
+        definition_order: DefinitionOrder::Reference,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    let positions: Vec<_> = ["Zebra", "Apple", "Kiwi", "Mango"]
+        .iter()
+        .map(|name| result.find(&format!("export type {} =", name)).unwrap())
+        .collect();
+    assert!(
+        positions.windows(2).all(|pair| pair[0] < pair[1]),
+        "Definitions not in reference order: {}",
+        result
+    );
+}
+
+/// Test sanitizing definition names that are not valid Luau identifiers
+#[test]
+fn test_invalid_identifier_type_names() {
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "code": { "$ref": "#/$defs/123abc" } },
+            "$defs": { "123abc": { "type": "string" } }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::new();
+    let (result, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    assert!(result.contains("    code: _123Abc?,"));
+    assert!(result.contains("export type _123Abc = string\n"));
+    assert_eq!(
+        diagnostics.warnings,
+        vec![
+            "type name '123Abc' for '123abc' is not a valid Luau identifier, emitted as '_123Abc'"
+                .to_string()
+        ]
+    );
+
+    // Luau identifiers are ASCII-only, so other letters are replaced too
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": { "name": { "$ref": "#/$defs/名前" } },
+            "$defs": { "名前": { "type": "string" } }
+        }"##,
+    )
+    .unwrap();
+    let (result, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+    assert!(
+        result.contains("    name: __?,\n"),
+        "Non-ASCII type name emitted: {}",
+        result
+    );
+    assert!(result.contains("export type __ = string\n"));
+    assert_eq!(
+        diagnostics.warnings,
+        vec![
+            "type name '名前' for '名前' is not a valid Luau identifier, emitted as '__'"
+                .to_string()
+        ]
+    );
+}
+
+/// Test that @default values are written as Luau literals
+#[test]
+fn test_default_luau_literals() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "config": {
+                "type": "object",
+                "default": { "name": "say \"hi\"\n", "tags": ["x"], "parent": null, "my-key": 1 }
+            }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        emit_default_summary: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.starts_with(
+            "--- @default { config = { [\"my-key\"] = 1, name = \"say \\\"hi\\\"\\n\", parent = nil, tags = { \"x\" } } }\n"
+        ),
+        "Default not written as a Luau literal: {}",
+        result
+    );
+}
+
+/// Test exporting only the types named in the export list
+#[test]
+fn test_export_types_whitelist() {
+    let schema = r##"{
+        "type": "object",
+        "properties": { "user": { "$ref": "#/$defs/User" } },
+        "$defs": {
+            "User": {
+                "type": "object",
+                "properties": { "address": { "$ref": "#/$defs/Address" } }
+            },
+            "Address": { "type": "string" }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        export_types: Some(["User".to_string()].into_iter().collect()),
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(result.contains("type Root = {\n"));
+    assert!(result.contains("\nexport type User = {\n"));
+    assert!(result.contains("\ntype Address = string\n"));
+    assert_eq!(result.matches("export type").count(), 1);
+}
+
+/// Test suffixing definition names that collide once PascalCased
+#[test]
+fn test_definition_name_collisions() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "first": { "$ref": "#/$defs/User" },
+            "second": { "$ref": "#/$defs/user" },
+            "third": { "$ref": "#/definitions/user_" }
+        },
+        "$defs": {
+            "User": { "type": "string" },
+            "user": { "type": "number" }
+        },
+        "definitions": {
+            "user_": { "type": "boolean" }
+        }
+    }"##;
+
+    let schema: JsonSchema = serde_json::from_str(schema).unwrap();
+    let (result, diagnostics) = SchemaConverter::new()
+        .convert_with_diagnostics(&schema, "Root")
+        .unwrap();
+
+    assert_eq!(
+        diagnostics.warnings,
+        vec![
+            "type name 'User' for 'user' collides with another type, emitted as 'User2'"
+                .to_string(),
+            "type name 'User' for 'user_' collides with another type, emitted as 'User3'"
+                .to_string()
+        ]
+    );
+    assert!(result.contains("    first: User?,\n    second: User2?,\n    third: User3?,"));
+    assert!(result.contains("export type User = string\n"));
+    assert!(result.contains("export type User2 = number\n"));
+    assert!(result.contains("export type User3 = boolean\n"));
+
+    // Suffixes skip names already used by another definition or by the root
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "a": { "$ref": "#/$defs/user" },
+            "b": { "$ref": "#/$defs/User2" },
+            "c": { "$ref": "#/$defs/root" }
+        },
+        "$defs": {
+            "User": { "type": "string" },
+            "user": { "type": "number" },
+            "User2": { "type": "boolean" },
+            "root": { "type": "integer" }
+        }
+    }"##;
+    let schema: JsonSchema = serde_json::from_str(schema).unwrap();
+    let (result, diagnostics) = SchemaConverter::new()
+        .convert_with_diagnostics(&schema, "Root")
+        .unwrap();
+    assert_eq!(
+        diagnostics.warnings,
+        vec![
+            "type name 'Root' for 'root' collides with another type, emitted as 'Root2'"
+                .to_string(),
+            "type name 'User' for 'user' collides with another type, emitted as 'User3'"
+                .to_string()
+        ]
+    );
+    assert!(
+        result.contains("    a: User3?,\n    b: User2?,\n    c: Root2?,"),
+        "Suffix reused a taken name: {}",
+        result
+    );
+    assert!(result.contains("export type User2 = boolean\n"));
+    assert!(result.contains("export type User3 = number\n"));
+    assert!(result.contains("export type Root2 = number\n"));
+
+    // Named union variants of the root are reserved too
+    let options = ConverterOptions {
+        named_union_variants: true,
+        ..Default::default()
+    };
+    let schema = r##"{
+        "oneOf": [
+            { "type": "object", "properties": { "a": { "type": "string" } } },
+            { "$ref": "#/$defs/RootVariant1" }
+        ],
+        "$defs": {
+            "RootVariant1": { "type": "object", "properties": { "b": { "type": "number" } } }
+        }
+    }"##;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("export type Root = RootVariant1 | RootVariant12\n"),
+        "Variant name collided with a definition: {}",
+        result
+    );
+    assert!(result.contains("export type RootVariant12 = {\n    b: number?,\n}\n"));
+}
+
+/// Test that properties differing only by case are both emitted
+#[test]
+fn test_case_distinct_properties() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "Name": { "type": "string" },
+            "name": { "type": "number" },
+            "nested": {
+                "type": "object",
+                "properties": { "Id": { "type": "string" }, "id": { "type": "boolean" } },
+                "required": ["Id", "id"]
+            }
+        },
+        "required": ["name"]
+    }"#;
+
+    let schema: JsonSchema = serde_json::from_str(schema).unwrap();
+    let (result, diagnostics) = SchemaConverter::new()
+        .convert_with_diagnostics(&schema, "Root")
+        .unwrap();
+
+    assert!(
+        result.contains("    Name: string?,\n    name: number,\n"),
+        "Case-distinct properties merged: {}",
+        result
+    );
+    assert!(result.contains("    nested: { Id: string, id: boolean }?,"));
+    assert!(diagnostics.warnings.is_empty());
+}
+
+/// Test wrapping @pattern regexes in a markdown code span
+#[test]
+fn test_pattern_code_span() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "slug": { "type": "string", "pattern": "^[a-z_]+\\*$" },
+            "quoted": { "type": "string", "pattern": "^`.*`$" }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    --- @pattern ^[a-z_]+\\*$\n"));
+
+    let options = ConverterOptions {
+        pattern_code_span: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("    --- @pattern `^[a-z_]+\\*$`\n    slug: string?,"),
+        "Pattern not wrapped: {}",
+        result
+    );
+    assert!(result.contains("    --- @pattern `` ^`.*`$ ``\n    quoted: string?,"));
+}
+
+/// Test grouping a string's constraints into one `@string` comment
+#[test]
+fn test_group_string_constraints() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "code": { "type": "string", "minLength": 5, "maxLength": 50, "pattern": "^[a-z]+$" }
+        },
+        "required": ["code"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains(
+        "    --- @minLength 5\n    --- @maxLength 50\n    --- @pattern ^[a-z]+$\n    code: string,"
+    ));
+
+    let options = ConverterOptions {
+        group_string_constraints: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains(
+            "    --- @string minLength=5 maxLength=50 pattern=^[a-z]+$\n    code: string,"
+        ),
+        "String constraints not grouped: {}",
+        result
+    );
+}
+
+/// Test hoisting a string enum shared by several properties into one named type
+#[test]
+fn test_hoist_shared_enums() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "status": { "type": "string", "enum": ["active", "disabled"] },
+            "previous": { "type": "string", "enum": ["active", "disabled"] },
+            "role": { "type": "string", "enum": ["admin", "member"] },
+            "owner": { "$ref": "#/$defs/Owner" }
+        },
+        "$defs": {
+            "Owner": {
+                "type": "object",
+                "properties": { "state": { "enum": ["active", "disabled"] } }
+            }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        hoist_shared_enums: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(result.contains("    previous: Previous?,\n"));
+    assert!(result.contains("    role: \"admin\" | \"member\"?,\n"));
+    assert!(result.contains("    status: Previous?,\n"));
+    assert!(result.contains("    state: Previous?,\n"));
+    assert_eq!(
+        result
+            .matches("export type Previous = \"active\" | \"disabled\"\n")
+            .count(),
+        1,
+        "Shared enum not hoisted once: {}",
+        result
+    );
+}
+
+/// Test configuring the blank lines between top-level declarations
+#[test]
+fn test_blank_lines_between() {
+    let schema = r##"{
+        "type": "object",
+        "properties": { "a": { "$ref": "#/$defs/A" } },
+        "$defs": {
+            "A": { "type": "string" },
+            "B": { "type": "number" }
+        }
+    }"##;
+
+    let options = ConverterOptions {
+        blank_lines_between: 2,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("}\n\n\nexport type A = string\n\n\nexport type B = number\n"),
+        "Unexpected spacing: {}",
+        result
+    );
+
+    let options = ConverterOptions {
+        blank_lines_between: 0,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.contains("}\nexport type A = string\nexport type B = number\n"));
+}
+
+/// Test an if/then/else keyed on a property const producing a tagged union
+#[test]
+fn test_conditional_discriminator_union() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "kind": { "type": "string" },
+            "id": { "type": "string" }
+        },
+        "required": ["kind", "id"],
+        "if": { "properties": { "kind": { "const": "a" } } },
+        "then": { "properties": { "alpha": { "type": "number" } }, "required": ["alpha"] },
+        "else": { "properties": { "beta": { "type": "boolean" } } }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains(
+            "--- Tagged union (if/then/else on `kind`)\nexport type Root = { alpha: number, id: string, kind: \"a\" } | { beta: boolean?, id: string, kind: string }\n"
+        ),
+        "Conditional not converted to a union: {}",
+        result
+    );
+
+    // An enum discriminator is narrowed in the else branch so the tags do not overlap
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "kind": { "enum": ["a", "b", "c"] }
+        },
+        "required": ["kind"],
+        "if": { "properties": { "kind": { "const": "a" } } },
+        "then": { "properties": { "alpha": { "type": "number" } }, "required": ["alpha"] },
+        "else": { "properties": { "beta": { "type": "boolean" } } }
+    }"#;
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains(
+            "export type Root = { alpha: number, kind: \"a\" } | { beta: boolean?, kind: \"b\" | \"c\" }\n"
+        ),
+        "Else branch tag not narrowed: {}",
+        result
+    );
+}
+
+/// Test an if/then without else narrowing an enum discriminator instead of adding an `any` branch
+#[test]
+fn test_conditional_without_else() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "kind": { "enum": ["a", "b", "c"] },
+            "id": { "type": "string" }
+        },
+        "required": ["kind", "id"],
+        "if": { "properties": { "kind": { "const": "a" } } },
+        "then": { "properties": { "alpha": { "type": "number" } }, "required": ["alpha"] }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains(
+            "export type Root = { alpha: number, id: string, kind: \"a\" } | { id: string, kind: \"b\" | \"c\" }\n"
+        ),
+        "Missing else not narrowed to the other tags: {}",
+        result
+    );
+    assert!(!result.contains("any"));
+
+    // A discriminator without an enum has no known complement, so the conditional is ignored
+    let schema: JsonSchema = serde_json::from_str(
+        r#"{
+            "type": "object",
+            "properties": { "kind": { "type": "string" } },
+            "required": ["kind"],
+            "if": { "properties": { "kind": { "const": "a" } } },
+            "then": { "properties": { "alpha": { "type": "number" } } }
+        }"#,
+    )
+    .unwrap();
+    let (result, diagnostics) = SchemaConverter::new()
+        .convert_with_diagnostics(&schema, "Root")
+        .unwrap();
+    assert!(result.contains("export type Root = {\n    kind: string,\n}\n"));
+    assert_eq!(diagnostics.unsupported_keywords[0].keyword, "if");
+}
+
+/// Test declared properties combined with a typed additionalProperties index
+#[test]
+fn test_properties_with_additional_properties() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "count": { "type": "number" },
+            "meta": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"],
+                "additionalProperties": { "type": "number" }
+            }
+        },
+        "required": ["name"],
+        "additionalProperties": { "type": "number" }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains(
+            "export type Root = {\n    count: number?,\n    meta: { id: string, [string]: number }?,\n    name: string,\n    [string]: number,\n}"
+        ),
+        "Unexpected record-with-extras type: {}",
+        result
+    );
+}
+
+/// Test collapsing unions with more members than the limit
+#[test]
+fn test_max_union_members() {
+    let members: Vec<String> = (0..50).map(|i| format!("\"value{}\"", i)).collect();
+    let schema: JsonSchema = serde_json::from_str(&format!(
+        r#"{{
+            "type": "object",
+            "properties": {{
+                "big": {{ "enum": [{}] }},
+                "mixed": {{ "anyOf": [{}] }},
+                "small": {{ "enum": ["a", "b"] }}
+            }}
+        }}"#,
+        members.join(", "),
+        (0..20)
+            .map(|i| if i % 2 == 0 {
+                r#"{ "type": "string" }"#
+            } else {
+                r#"{ "type": "number" }"#
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+    .unwrap();
+
+    let converter = SchemaConverter::with_options(ConverterOptions {
+        max_union_members: Some(10),
+        ..Default::default()
+    });
+    let (result, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    assert!(
+        result.contains("    big: string?,\n"),
+        "Enum not collapsed: {}",
+        result
+    );
+    assert!(result.contains("    mixed: any?,\n"));
+    assert!(result.contains("    small: \"a\" | \"b\"?,\n"));
+    assert_eq!(
+        diagnostics.warnings,
+        vec![
+            "union for 'enum' has 50 members, more than the limit of 10; collapsed to string"
+                .to_string(),
+            "union for 'inline union' has 20 members, more than the limit of 10; collapsed to any"
+                .to_string(),
+        ]
+    );
+}
+
+/// Test exclusive bounds printing whole numbers cleanly
+#[test]
+fn test_exclusive_bounds_formatting() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "ratio": { "type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1.5 },
+            "score": { "type": "integer", "exclusiveMinimum": -10.0, "exclusiveMaximum": 100.0 }
+        }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("    --- @exclusiveMinimum 0\n    --- @exclusiveMaximum 1.5\n    ratio:")
+    );
+    assert!(
+        result.contains("    --- @exclusiveMinimum -10\n    --- @exclusiveMaximum 100\n    score:"),
+        "Unexpected exclusive bounds: {}",
+        result
+    );
+}
+
+/// Test reporting keywords that could not be fully represented with their paths
+#[test]
+fn test_unsupported_keywords_report() {
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "not": { "const": "admin" } },
+                "id": { "type": ["string", "number"], "not": { "type": "number" } },
+                "tags": {
+                    "type": "object",
+                    "patternProperties": { "^x-": { "type": "string" } }
+                },
+                "meta": {
+                    "type": "object",
+                    "propertyNames": { "pattern": "^[a-z]+$" },
+                    "dependentRequired": { "a": ["b"] },
+                    "x-owner": "core"
+                }
+            },
+            "$defs": {
+                "a/b": { "not": { "type": "string" } }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::new();
+    let (_, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    let unsupported = |keyword: &str, path: &str| UnsupportedKeyword {
+        keyword: keyword.to_string(),
+        path: path.to_string(),
+    };
+    assert_eq!(
+        diagnostics.unsupported_keywords,
+        vec![
+            unsupported("dependentRequired", "#/properties/meta"),
+            unsupported("propertyNames", "#/properties/meta"),
+            unsupported("not", "#/properties/name"),
+            unsupported("patternProperties", "#/properties/tags"),
+            unsupported("not", "#/$defs/a~1b"),
+        ]
+    );
+}
+
+/// Test the optional marker on open-ended any fields
+#[test]
+fn test_optional_any_marker() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "x": {},
+            "y": true,
+            "z": { "type": "string" },
+            "nested": { "type": "object", "properties": { "w": {} } }
+        },
+        "required": ["y"]
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(
+        result.contains("    nested: { w: any? }?,\n    x: any?,\n    y: any,\n    z: string?,")
+    );
+
+    let options = ConverterOptions {
+        mark_optional_any: false,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("    nested: { w: any }?,\n    x: any,\n    y: any,\n    z: string?,"),
+        "Optional any fields still marked: {}",
+        result
+    );
+}
+
+/// Test truncating examples beyond the cap with an indicator
+#[test]
+fn test_examples_truncation() {
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "code": { "type": "string", "examples": ["a", "b", "c", "d", "e"] }
+        }
+    }"#;
+
+    let options = ConverterOptions {
+        emit_examples: true,
+        max_examples: Some(2),
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+
+    assert!(
+        result.contains(
+            "    --- @example \"a\"\n    --- @example \"b\"\n    --- @example ... (+3 more)\n    code: string?,"
+        ),
+        "Examples not truncated: {}",
+        result
+    );
+}
+
+/// Test naming a titled primitive root and definitions after their titles
+#[test]
+fn test_title_names() {
+    let options = ConverterOptions {
+        title_names: true,
+        ..Default::default()
+    };
+
+    let schema = r#"{ "type": "string", "title": "Email", "format": "email", "maxLength": 254 }"#;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.starts_with("--- @maxLength 254\n--- @format email\nexport type Email = string\n"),
+        "Titled root not named: {}",
+        result
+    );
+
+    // Titles that collide are suffixed like colliding keys
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "a": { "$ref": "#/$defs/First" },
+            "b": { "$ref": "#/$defs/Second" }
+        },
+        "$defs": {
+            "First": { "type": "string", "title": "user" },
+            "Second": { "type": "number", "title": "User" }
+        }
+    }"##;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.contains("    a: User?,\n    b: User2?,"));
+    assert!(result.contains("export type User = string\n"));
+    assert!(result.contains("export type User2 = number\n"));
+}
+
+/// Test emitting x- vendor extensions as comments
+#[test]
+fn test_vendor_extensions() {
+    let schema = r#"{
+        "type": "object",
+        "x-internal": true,
+        "properties": {
+            "token": { "type": "string", "x-since": "1.2.0", "x-owner": { "team": "auth" } }
+        },
+        "dependentRequired": { "token": [] }
+    }"#;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(!result.contains("@since"));
+
+    let options = ConverterOptions {
+        emit_extensions: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(result.starts_with("--- @internal true\nexport type Root = {\n"));
+    assert!(
+        result.contains(
+            "    --- @owner {\"team\":\"auth\"}\n    --- @since 1.2.0\n    token: string?,"
+        ),
+        "Extensions not emitted: {}",
+        result
+    );
+    assert!(!result.contains("dependentRequired"));
+
+    // Multi-line values keep the comment prefix on every line
+    let schema = r#"{ "type": "string", "x-note": "first\nsecond" }"#;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.starts_with("--- @note first\n--- second\nexport type Root = string\n"),
+        "Multi-line extension left the comment: {}",
+        result
+    );
+}
+
+/// Test dropping any members from intersections and collapsing never
+#[test]
+fn test_intersection_any_simplification() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "inline": {
+                "allOf": [true, { "type": "object", "properties": { "a": { "type": "string" } } }]
+            }
+        },
+        "$defs": {
+            "Open": {
+                "allOf": [{}, { "type": "object", "properties": { "a": { "type": "string" } } }]
+            },
+            "Impossible": { "allOf": [false, { "type": "string" }] }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+
+    assert!(
+        result.contains("    inline: { a: string? }?,"),
+        "Unexpected inline intersection: {}",
+        result
+    );
+    assert!(result.contains("export type Open = { a: string? }\n"));
+    assert!(result.contains("export type Impossible = never\n"));
+    assert!(!result.contains("any &"));
+}
+
+/// Test counting types, references and constraint comments
+#[test]
+fn test_conversion_stats() {
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1, "maxLength": 20 },
+                "role": { "enum": ["admin", "member"] },
+                "version": { "const": 2 },
+                "tags": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                "address": { "$ref": "#/$defs/Address" },
+                "meta": { "type": "object", "properties": { "id": { "type": "string" } } }
+            },
+            "$defs": {
+                "Address": { "type": "object", "properties": { "city": { "type": "string" } } }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let converter = SchemaConverter::new();
+    let (_, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    assert_eq!(
+        diagnostics.stats,
+        ConversionStats {
+            object_types: 3,
+            array_types: 1,
+            enum_types: 1,
+            const_types: 1,
+            refs_resolved: 1,
+            constraint_comments: 4,
+        }
+    );
+
+    // Types converted only for a `@not` comment are not counted
+    let schema: JsonSchema = serde_json::from_str(
+        r##"{
+            "type": "object",
+            "properties": {
+                "status": { "type": "string", "not": { "enum": ["banned"] } },
+                "owner": { "not": { "type": "object", "properties": { "id": { "type": "string" } } } }
+            }
+        }"##,
+    )
+    .unwrap();
+    let (output, diagnostics) = converter.convert_with_diagnostics(&schema, "Root").unwrap();
+
+    assert!(output.contains("--- @not \"banned\""));
+    assert_eq!(diagnostics.stats.enum_types, 0);
+    assert_eq!(diagnostics.stats.object_types, 1);
+}
+
+/// Test unwrapping objects that wrap a single required property
+#[test]
+fn test_unwrap_single_property() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "id": {
+                "type": "object",
+                "properties": { "value": { "type": "string" } },
+                "required": ["value"]
+            },
+            "note": {
+                "type": "object",
+                "properties": { "value": { "type": "string" } }
+            },
+            "count": { "$ref": "#/$defs/Count" }
+        },
+        "required": ["id"],
+        "$defs": {
+            "Count": {
+                "type": "object",
+                "properties": { "total": { "type": "integer" } },
+                "required": ["total"]
+            }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(result.contains("    id: { value: string },"));
+
+    let options = ConverterOptions {
+        unwrap_single_property: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("    --- @unwrapped value\n    id: string,"),
+        "Wrapper not unwrapped: {}",
+        result
+    );
+    // Optional wrapped keys keep their table
+    assert!(result.contains("    note: { value: string? }?,"));
+    assert!(result.contains("--- @unwrapped total\nexport type Count = number\n"));
+}
+
+/// Test the banner comment written before the first definition
+#[test]
+fn test_definitions_banner() {
+    let options = ConverterOptions {
+        definitions_banner: Some("=== Definitions ===".to_string()),
+        ..Default::default()
+    };
+
+    let schema = r##"{
+        "type": "object",
+        "properties": { "tag": { "$ref": "#/$defs/Tag" } },
+        "$defs": {
+            "Tag": { "type": "string" },
+            "Name": { "type": "string" }
+        }
+    }"##;
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("}\n\n--- === Definitions ===\n\nexport type Name = string\n"),
+        "Banner missing: {}",
+        result
+    );
+    assert_eq!(result.matches("=== Definitions ===").count(), 1);
+
+    // No banner without definitions
+    let result = convert_schema_with_options(r#"{ "type": "string" }"#, &options).unwrap();
+    assert!(!result.contains("Definitions"));
+}
+
+/// Test the `@jsonschema` comment holding each type's constraints as JSON
+#[test]
+fn test_schema_metadata_comment() {
+    let schema = r##"{
+        "type": "object",
+        "properties": { "score": { "$ref": "#/$defs/Score" } },
+        "$defs": {
+            "Score": { "type": "number", "minimum": 0, "maximum": 100.5, "description": "Score" }
+        }
+    }"##;
+
+    let result = convert_schema(schema).unwrap();
+    assert!(!result.contains("@jsonschema"));
+
+    let options = ConverterOptions {
+        emit_schema_metadata: true,
+        ..Default::default()
+    };
+    let result = convert_schema_with_options(schema, &options).unwrap();
+    assert!(
+        result.contains("--- Score\n--- @jsonschema {\"maximum\":100.5,\"minimum\":0}\n"),
+        "Metadata block missing: {}",
+        result
+    );
+    // Types without constraints get no block
+    assert_eq!(result.matches("@jsonschema").count(), 1);
+}